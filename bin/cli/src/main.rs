@@ -1,9 +1,13 @@
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 use clap::{Parser, Subcommand, ValueEnum};
 use mevboost_relay_api::{
-    types::{BuilderBidsReceivedOptions, PayloadDeliveredQueryOptions},
-    Client,
+    primitives::Hash32,
+    types::{
+        BuilderBidsReceivedOptions, BuilderBlockBidtrace, PayloadBidtrace,
+        PayloadDeliveredQueryOptions,
+    },
+    Client, Network,
 };
 
 #[derive(Parser)]
@@ -20,6 +24,9 @@ struct Args {
     /// will default to the current working directory.
     #[clap(long, short = 'p')]
     path: Option<String>,
+    /// The network whose relays to query: mainnet, holesky, sepolia or hoodi.
+    #[clap(long, short = 'n', default_value = "mainnet")]
+    network: Network,
 }
 
 #[derive(Default, ValueEnum, Clone)]
@@ -45,12 +52,16 @@ enum Command {
         #[clap(long)]
         slot: Option<u64>,
         #[clap(long)]
-        block_hash: Option<String>,
+        block_hash: Option<Hash32>,
     },
 
     /// Get the timestamp of the winning bid for a given slot.
     #[clap(name = "winning-bid-timestamp")]
     WinningBidTimestamp { slot: u64 },
+
+    /// Check the status of all relays. Exits with an error if any relay is down.
+    #[clap(name = "health")]
+    Health,
 }
 
 #[tokio::main]
@@ -58,7 +69,7 @@ async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
     let _ = tracing_subscriber::fmt::try_init();
 
-    let client = Client::default();
+    let client = Client::for_network(args.network);
 
     let mut output_file_path = args
         .path
@@ -75,7 +86,7 @@ async fn main() -> anyhow::Result<()> {
                 .await?;
 
             match args.output {
-                OutputMethod::Human => println!("{:#?}", &payloads),
+                OutputMethod::Human => print_payloads(&payloads),
                 OutputMethod::Csv => unimplemented!(),
                 OutputMethod::Json => {
                     output_file_path = output_file_path
@@ -94,7 +105,7 @@ async fn main() -> anyhow::Result<()> {
             let block_bids = client
                 .get_builder_blocks_received_on_all_relays(&BuilderBidsReceivedOptions {
                     slot,
-                    block_hash: block_hash.clone(),
+                    block_hash,
                     ..Default::default()
                 })
                 .await?;
@@ -105,8 +116,8 @@ async fn main() -> anyhow::Result<()> {
                 format!(
                     "block-hash-{}",
                     block_hash
-                        .as_ref()
                         .unwrap()
+                        .to_string()
                         .chars()
                         .take(8)
                         .collect::<String>()
@@ -115,7 +126,7 @@ async fn main() -> anyhow::Result<()> {
             output_file_path = output_file_path.join(format!("block-bids-{}", query_name));
 
             match args.output {
-                OutputMethod::Human => println!("{:#?}", &block_bids),
+                OutputMethod::Human => print_block_bids(&block_bids),
                 OutputMethod::Csv => unimplemented!(),
                 OutputMethod::Json => {
                     for (relay, bids) in block_bids {
@@ -144,10 +155,10 @@ async fn main() -> anyhow::Result<()> {
                     continue;
                 }
 
-                let block_hash = relay_payloads[0].block_hash.clone();
+                let block_hash = relay_payloads[0].bid_trace.block_hash;
                 let block_bids = client
                     .get_builder_blocks_received(
-                        relay,
+                        &relay,
                         &BuilderBidsReceivedOptions {
                             slot: Some(slot),
                             block_hash: Some(block_hash),
@@ -157,18 +168,103 @@ async fn main() -> anyhow::Result<()> {
                     .await?;
 
                 let timestamp = block_bids[0].timestamp_ms;
+                let ms_into_slot = block_bids[0].ms_into_slot(client.chain_spec());
                 println!(
-                    "The winning bid for slot {} was submitted to {} at: {}",
-                    slot, relay, timestamp
+                    "The winning bid for slot {} was submitted to {} at: {} ({} ms into the slot)",
+                    slot, relay, timestamp, ms_into_slot
                 )
             }
         }
+
+        Command::Health => {
+            let health = client.health_check_all().await;
+
+            let mut statuses = health
+                .iter()
+                .map(|(relay, response)| RelayStatus {
+                    relay: relay.to_string(),
+                    up: response.is_ok(),
+                    latency_ms: response.latency.as_millis(),
+                    error: response.result.as_ref().err().map(ToString::to_string),
+                })
+                .collect::<Vec<_>>();
+            statuses.sort_by(|a, b| a.relay.cmp(&b.relay));
+
+            match args.output {
+                OutputMethod::Human => {
+                    for status in &statuses {
+                        let state = if status.up { "up" } else { "DOWN" };
+                        print!(
+                            "{:<24} {:<4} {:>6} ms",
+                            status.relay, state, status.latency_ms
+                        );
+                        match &status.error {
+                            Some(error) => println!("  {}", error),
+                            None => println!(),
+                        }
+                    }
+                }
+                OutputMethod::Csv => {
+                    write_csv(output_file_path.join("health.csv"), statuses)?;
+                }
+                OutputMethod::Json => {
+                    write_json(output_file_path.join("health.json"), statuses)?;
+                }
+            }
+
+            let summary = health.summary();
+            if summary.failed > 0 {
+                anyhow::bail!("{}", summary);
+            }
+        }
     }
 
     Ok(())
 }
 
-#[allow(unused)]
+/// Status of a relay, as reported by the `health` command.
+#[derive(serde::Serialize)]
+struct RelayStatus {
+    relay: String,
+    up: bool,
+    latency_ms: u128,
+    error: Option<String>,
+}
+
+/// Print the payloads delivered by each relay, with values in ETH.
+fn print_payloads(payloads: &HashMap<String, Vec<PayloadBidtrace>>) {
+    for (relay, relay_payloads) in payloads {
+        println!("{} ({} payloads)", relay, relay_payloads.len());
+        for payload in relay_payloads {
+            println!(
+                "  slot {} block {} ({}): {} ETH from builder {}",
+                payload.bid_trace.slot,
+                payload.block_number,
+                payload.bid_trace.block_hash,
+                payload.bid_trace.value.format_eth(),
+                payload.bid_trace.builder_pubkey
+            );
+        }
+    }
+}
+
+/// Print the block bids received by each relay, with values in ETH.
+fn print_block_bids(block_bids: &HashMap<String, Vec<BuilderBlockBidtrace>>) {
+    for (relay, bids) in block_bids {
+        println!("{} ({} bids)", relay, bids.len());
+        for bid in bids {
+            println!(
+                "  slot {} block {} at {} ms: {} ETH from builder {}",
+                bid.payload.bid_trace.slot,
+                bid.payload.bid_trace.block_hash,
+                bid.timestamp_ms,
+                bid.payload.bid_trace.value.format_eth(),
+                bid.payload.bid_trace.builder_pubkey
+            );
+        }
+    }
+}
+
 fn write_csv<T: serde::Serialize>(path: impl AsRef<Path>, data: Vec<T>) -> anyhow::Result<()> {
     let path = path.as_ref();
     if let Some(parent) = path.parent() {
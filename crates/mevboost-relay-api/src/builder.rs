@@ -0,0 +1,281 @@
+use std::{collections::HashMap, time::Duration};
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::{
+    constants, primitives::ForkVersion, rate_limit::RateLimiter, relay, ChainSpec, Client, Network,
+    RateLimit, Relay, Result, RetryPolicy,
+};
+
+/// Builder for a [`Client`] with custom relays and HTTP settings.
+///
+/// ```no_run
+/// # fn example() -> mevboost_relay_api::Result<()> {
+/// use std::time::Duration;
+///
+/// let client = mevboost_relay_api::Client::builder()
+///     .timeout(Duration::from_secs(5))
+///     .user_agent("my-service/1.0")
+///     .proxy(mevboost_relay_api::reqwest::Proxy::all("http://egress-proxy:3128")?)
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ClientBuilder {
+    /// Relays to use for queries, by name.
+    relays: HashMap<String, Relay>,
+    /// Custom HTTP client. If set, all HTTP settings below are ignored.
+    http_client: Option<reqwest::Client>,
+    /// Total timeout of a single HTTP request.
+    timeout: Option<Duration>,
+    /// Timeout for the connect phase of a single HTTP request.
+    connect_timeout: Option<Duration>,
+    /// Value of the `User-Agent` header.
+    user_agent: String,
+    /// Headers added to every request.
+    default_headers: HeaderMap,
+    /// Proxies to route requests through.
+    proxies: Vec<reqwest::Proxy>,
+    /// Additional trusted root certificates.
+    root_certificates: Vec<reqwest::Certificate>,
+    /// Whether to skip TLS certificate validation.
+    accept_invalid_certs: bool,
+    /// Maximum number of relays queried concurrently by multi-relay methods.
+    max_concurrency: usize,
+    /// Timeout for a single relay query in multi-relay methods.
+    relay_timeout: Duration,
+    /// Policy for retrying failed requests.
+    retry_policy: RetryPolicy,
+    /// Policy for retrying failed block submissions and validator registrations.
+    submission_retry_policy: RetryPolicy,
+    /// Rate limit applied to relays without a specific limit.
+    default_rate_limit: Option<RateLimit>,
+    /// Chain spec of the network of the relays.
+    spec: ChainSpec,
+    /// Whether to verify the signatures of the validator registrations returned by relays.
+    verify_registrations: bool,
+    /// Whether to verify that the bids returned by relays are signed by the relay.
+    verify_bids: bool,
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self::with_relays(relay::default_relays())
+    }
+}
+
+impl ClientBuilder {
+    /// Create a new builder with a custom list of relays.
+    ///
+    /// See [`constants::DEFAULT_RELAYS`] for an example of relay URLs.
+    pub fn with_relays(relays: impl IntoIterator<Item = Relay>) -> Self {
+        Self {
+            relays: relays
+                .into_iter()
+                .map(|relay| (relay.name.clone(), relay))
+                .collect(),
+            http_client: None,
+            timeout: Some(constants::DEFAULT_REQUEST_TIMEOUT),
+            connect_timeout: Some(constants::DEFAULT_CONNECT_TIMEOUT),
+            user_agent: constants::DEFAULT_USER_AGENT.to_string(),
+            default_headers: HeaderMap::new(),
+            proxies: Vec::new(),
+            root_certificates: Vec::new(),
+            accept_invalid_certs: false,
+            max_concurrency: constants::DEFAULT_MAX_CONCURRENCY,
+            relay_timeout: constants::DEFAULT_RELAY_TIMEOUT,
+            retry_policy: RetryPolicy::default(),
+            submission_retry_policy: RetryPolicy::none(),
+            default_rate_limit: None,
+            spec: ChainSpec::default(),
+            verify_registrations: false,
+            verify_bids: false,
+        }
+    }
+
+    /// Create a new builder with the relays and chain spec of the given network.
+    pub fn for_network(network: Network) -> Self {
+        Self::with_relays(network.relays()).chain_spec(network.chain_spec())
+    }
+
+    /// Add a relay, replacing any relay with the same name.
+    pub fn relay(mut self, relay: Relay) -> Self {
+        self.relays.insert(relay.name.clone(), relay);
+        self
+    }
+
+    /// Use a custom [`reqwest::Client`] for all requests.
+    ///
+    /// When set, the HTTP settings of this builder (timeouts, user agent, default headers,
+    /// proxies and TLS settings) are ignored and must be configured on the given client instead.
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Set the total timeout of a single HTTP request. `None` disables the timeout.
+    pub fn timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.timeout = timeout.into();
+        self
+    }
+
+    /// Set the timeout for the connect phase of a single HTTP request. `None` disables the timeout.
+    pub fn connect_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.connect_timeout = timeout.into();
+        self
+    }
+
+    /// Set the value of the `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Add a header that is sent with every request.
+    pub fn default_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.default_headers.insert(name, value);
+        self
+    }
+
+    /// Add a set of headers that are sent with every request.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+        self
+    }
+
+    /// Route requests through the given proxy. Can be called multiple times.
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Trust an additional root certificate when validating TLS connections.
+    pub fn add_root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Skip TLS certificate validation.
+    ///
+    /// **Warning**: this makes the client vulnerable to man-in-the-middle attacks.
+    /// Only use this for testing against local relays.
+    pub fn danger_accept_invalid_certs(mut self, accept_invalid_certs: bool) -> Self {
+        self.accept_invalid_certs = accept_invalid_certs;
+        self
+    }
+
+    /// Set the maximum number of relays that are queried concurrently
+    /// by the `*_on_all_relays` methods. Values lower than 1 are treated as 1.
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Set the timeout for a single relay query performed by the `*_on_all_relays` methods.
+    pub fn relay_timeout(mut self, relay_timeout: Duration) -> Self {
+        self.relay_timeout = relay_timeout;
+        self
+    }
+
+    /// Set the policy for retrying failed requests. Use [`RetryPolicy::none()`] to disable retries.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the policy for retrying failed block submissions and validator registrations.
+    ///
+    /// These requests are not idempotent, so they are not retried by default
+    /// ([`RetryPolicy::none()`]) and are not affected by [`ClientBuilder::retry_policy()`].
+    pub fn submission_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.submission_retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the rate limit for requests to the relay with the given name, if present.
+    /// `None` makes the relay fall back to the default rate limit.
+    ///
+    /// See [`constants::DEFAULT_RATE_LIMITS`] for the limits applied to the default relays.
+    pub fn rate_limit(mut self, relay_name: &str, limit: Option<RateLimit>) -> Self {
+        if let Some(relay) = self.relays.get_mut(relay_name) {
+            relay.options.rate_limit = limit;
+        }
+        self
+    }
+
+    /// Set the rate limit applied to relays without a specific limit. Defaults to `None` (unlimited).
+    pub fn default_rate_limit(mut self, limit: Option<RateLimit>) -> Self {
+        self.default_rate_limit = limit;
+        self
+    }
+
+    /// Set the genesis fork version of the network of the relays, used to verify signatures.
+    /// Defaults to [`constants::MAINNET_GENESIS_FORK_VERSION`].
+    pub fn genesis_fork_version(mut self, genesis_fork_version: ForkVersion) -> Self {
+        self.spec.genesis_fork_version = genesis_fork_version;
+        self
+    }
+
+    /// Set the chain spec of the network of the relays. See [`Client::with_chain_spec()`].
+    pub fn chain_spec(mut self, spec: ChainSpec) -> Self {
+        self.spec = spec;
+        self
+    }
+
+    /// Verify the signatures of the validator registrations returned by relays.
+    /// See [`Client::with_registration_verification()`].
+    pub fn verify_registrations(mut self, verify_registrations: bool) -> Self {
+        self.verify_registrations = verify_registrations;
+        self
+    }
+
+    /// Verify that the bids returned by relays are signed by the relay. Disabled by default.
+    /// See [`Client::with_bid_verification()`].
+    pub fn verify_bids(mut self, verify_bids: bool) -> Self {
+        self.verify_bids = verify_bids;
+        self
+    }
+
+    /// Build the client. Fails if the HTTP client cannot be initialized,
+    /// for example because of an invalid TLS configuration.
+    pub fn build(self) -> Result<Client> {
+        let inner = match self.http_client {
+            Some(client) => client,
+            None => {
+                let mut builder = reqwest::Client::builder()
+                    .user_agent(self.user_agent)
+                    .default_headers(self.default_headers)
+                    .danger_accept_invalid_certs(self.accept_invalid_certs);
+
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                for proxy in self.proxies {
+                    builder = builder.proxy(proxy);
+                }
+                for certificate in self.root_certificates {
+                    builder = builder.add_root_certificate(certificate);
+                }
+
+                builder.build()?
+            }
+        };
+
+        Ok(Client {
+            relays: self.relays,
+            inner,
+            max_concurrency: self.max_concurrency,
+            relay_timeout: self.relay_timeout,
+            retry_policy: self.retry_policy,
+            submission_retry_policy: self.submission_retry_policy,
+            rate_limiter: RateLimiter::new(self.default_rate_limit),
+            spec: self.spec,
+            verify_registrations: self.verify_registrations,
+            verify_bids: self.verify_bids,
+        })
+    }
+}
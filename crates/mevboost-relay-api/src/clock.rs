@@ -0,0 +1,156 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::ChainSpec;
+
+/// Maps wall-clock time to the slots and epochs of a chain.
+///
+/// ```
+/// use std::time::Duration;
+/// use mevboost_relay_api::{clock::SlotClock, Network};
+///
+/// let clock = SlotClock::new(&Network::Mainnet.chain_spec());
+/// let time = clock.slot_start(1_000) + Duration::from_secs(3);
+///
+/// assert_eq!(clock.slot_at(time), Some(1_000));
+/// assert_eq!(clock.time_into_slot(time), Some(Duration::from_secs(3)));
+/// assert_eq!(clock.epoch_of_slot(1_000), 31);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotClock {
+    /// Start of slot 0.
+    genesis: SystemTime,
+    /// Duration of a slot.
+    slot_duration: Duration,
+    /// Spec of the chain, for the epochs of slots.
+    spec: ChainSpec,
+}
+
+impl SlotClock {
+    /// Create a clock for the chain with the given spec.
+    pub fn new(spec: &ChainSpec) -> Self {
+        Self {
+            genesis: UNIX_EPOCH + Duration::from_secs(spec.genesis_time),
+            slot_duration: Duration::from_secs(spec.seconds_per_slot()),
+            spec: spec.clone(),
+        }
+    }
+
+    /// Returns the duration of a slot.
+    pub fn slot_duration(&self) -> Duration {
+        self.slot_duration
+    }
+
+    /// Returns the slot at the given time, or `None` before genesis.
+    pub fn slot_at(&self, time: SystemTime) -> Option<u64> {
+        let since_genesis = time.duration_since(self.genesis).ok()?;
+        Some((since_genesis.as_millis() / self.slot_duration.as_millis()) as u64)
+    }
+
+    /// Returns the current slot, or `None` before genesis.
+    pub fn current_slot(&self) -> Option<u64> {
+        self.slot_at(SystemTime::now())
+    }
+
+    /// Returns the start time of the given slot.
+    pub fn slot_start(&self, slot: u64) -> SystemTime {
+        let since_genesis_ms = (self.slot_duration.as_millis() as u64).saturating_mul(slot);
+        self.genesis + Duration::from_millis(since_genesis_ms)
+    }
+
+    /// Returns the start time of the given slot, in milliseconds since the Unix epoch.
+    pub fn slot_start_ms(&self, slot: u64) -> u128 {
+        self.slot_start(slot)
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+    }
+
+    /// Returns the time elapsed since the start of the slot at the given time,
+    /// or `None` before genesis.
+    pub fn time_into_slot(&self, time: SystemTime) -> Option<Duration> {
+        let since_genesis = time.duration_since(self.genesis).ok()?;
+        let into_slot = since_genesis.as_nanos() % self.slot_duration.as_nanos();
+        Some(Duration::from_nanos(into_slot as u64))
+    }
+
+    /// Returns the time elapsed since the start of the current slot, or `None` before genesis.
+    pub fn current_time_into_slot(&self) -> Option<Duration> {
+        self.time_into_slot(SystemTime::now())
+    }
+
+    /// Returns the epoch of the given slot. See [`ChainSpec::epoch_of_slot()`].
+    pub fn epoch_of_slot(&self, slot: u64) -> u64 {
+        self.spec.epoch_of_slot(slot)
+    }
+
+    /// Returns the epoch at the given time, or `None` before genesis.
+    pub fn epoch_at(&self, time: SystemTime) -> Option<u64> {
+        self.slot_at(time).map(|slot| self.epoch_of_slot(slot))
+    }
+
+    /// Returns the current epoch, or `None` before genesis.
+    pub fn current_epoch(&self) -> Option<u64> {
+        self.epoch_at(SystemTime::now())
+    }
+
+    /// Returns the number of milliseconds between the start of the given slot and the given
+    /// timestamp in milliseconds since the Unix epoch. Negative if the timestamp is before
+    /// the start of the slot. Saturates at the bounds of `i64`.
+    pub fn ms_into_slot(&self, slot: u64, timestamp_ms: u128) -> i64 {
+        let ms =
+            i128::try_from(timestamp_ms).unwrap_or(i128::MAX) - self.slot_start_ms(slot) as i128;
+        i64::try_from(ms).unwrap_or(if ms < 0 { i64::MIN } else { i64::MAX })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::SlotClock;
+    use crate::{ChainSpec, Network};
+
+    #[test]
+    fn test_slot_clock_mainnet() {
+        let clock = SlotClock::new(&Network::Mainnet.chain_spec());
+
+        // Slot 8_000_000 started at 2023-12-17 14:40:23 UTC.
+        let start = UNIX_EPOCH + Duration::from_secs(1_702_824_023);
+        assert_eq!(clock.slot_start(8_000_000), start);
+        assert_eq!(clock.slot_start_ms(8_000_000), 1_702_824_023_000);
+        assert_eq!(clock.slot_at(start), Some(8_000_000));
+        assert_eq!(
+            clock.slot_at(start - Duration::from_millis(1)),
+            Some(7_999_999)
+        );
+        assert_eq!(
+            clock.time_into_slot(start + Duration::from_millis(11_999)),
+            Some(Duration::from_millis(11_999))
+        );
+        assert_eq!(clock.epoch_of_slot(8_000_000), 250_000);
+
+        assert_eq!(clock.epoch_at(start), Some(250_000));
+        assert_eq!(
+            clock.epoch_at(start - Duration::from_millis(1)),
+            Some(249_999)
+        );
+
+        assert_eq!(clock.slot_at(UNIX_EPOCH), None);
+        assert_eq!(clock.time_into_slot(UNIX_EPOCH), None);
+        assert_eq!(clock.epoch_at(UNIX_EPOCH), None);
+    }
+
+    #[test]
+    fn test_slot_clock_ms_into_slot() {
+        let spec = ChainSpec::default()
+            .with_genesis_time(1_000)
+            .with_slot_timing(6, 32)
+            .unwrap();
+        let clock = SlotClock::new(&spec);
+
+        assert_eq!(clock.ms_into_slot(2, 1_012_500), 500);
+        assert_eq!(clock.ms_into_slot(2, 1_011_000), -1_000);
+        assert_eq!(clock.ms_into_slot(2, u128::MAX), i64::MAX);
+        assert_eq!(clock.ms_into_slot(u64::MAX, 0), i64::MIN);
+    }
+}
@@ -0,0 +1,367 @@
+use std::{
+    fmt,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    primitives::{
+        quoted_u256, quoted_u64, Address, Bloom, BlsPublicKey, BlsSignature, Bytes, Hash32,
+        KzgCommitment, Wei, U256,
+    },
+    ChainSpec,
+};
+
+mod block;
+pub use block::*;
+
+/// Consensus fork of the types exchanged with relays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Fork {
+    /// The Capella fork.
+    Capella,
+    /// The Deneb fork.
+    Deneb,
+    /// The Electra fork.
+    Electra,
+}
+
+impl fmt::Display for Fork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Capella => f.write_str("capella"),
+            Self::Deneb => f.write_str("deneb"),
+            Self::Electra => f.write_str("electra"),
+        }
+    }
+}
+
+/// A message signed with a BLS signature.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct Signed<T> {
+    pub message: T,
+    pub signature: BlsSignature,
+}
+
+/// Registration of a validator with a builder or relay, with its preferences
+/// for the blocks built for it.
+///
+/// [Visit the docs](https://ethereum.github.io/builder-specs/#/Builder/registerValidator) for more info.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct ValidatorRegistration {
+    /// Address to receive the block rewards.
+    pub fee_recipient: Address,
+    /// Preferred gas limit of the blocks.
+    #[serde(with = "quoted_u64")]
+    pub gas_limit: u64,
+    /// Unix timestamp of the registration, in seconds.
+    #[serde(with = "quoted_u64")]
+    pub timestamp: u64,
+    /// Public key of the validator.
+    pub pubkey: BlsPublicKey,
+}
+
+impl ValidatorRegistration {
+    /// Returns the timestamp of the registration as a date, or `None` if it is out of range.
+    pub fn datetime(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(i64::try_from(self.timestamp).ok()?, 0)
+    }
+
+    /// Returns the slot during which the registration was signed, or `None` if its
+    /// timestamp is before the genesis of the chain.
+    pub fn slot(&self, spec: &ChainSpec) -> Option<u64> {
+        spec.slot_clock()
+            .slot_at(UNIX_EPOCH + Duration::from_secs(self.timestamp))
+    }
+}
+
+/// Validator registration signed by the validator.
+pub type SignedValidatorRegistration = Signed<ValidatorRegistration>;
+
+/// Execution payload header of the Capella fork.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct ExecutionPayloadHeaderCapella {
+    pub parent_hash: Hash32,
+    pub fee_recipient: Address,
+    pub state_root: Hash32,
+    pub receipts_root: Hash32,
+    pub logs_bloom: Bloom,
+    pub prev_randao: Hash32,
+    #[serde(with = "quoted_u64")]
+    pub block_number: u64,
+    #[serde(with = "quoted_u64")]
+    pub gas_limit: u64,
+    #[serde(with = "quoted_u64")]
+    pub gas_used: u64,
+    #[serde(with = "quoted_u64")]
+    pub timestamp: u64,
+    pub extra_data: Bytes,
+    #[serde(with = "quoted_u256")]
+    pub base_fee_per_gas: U256,
+    pub block_hash: Hash32,
+    pub transactions_root: Hash32,
+    pub withdrawals_root: Hash32,
+}
+
+/// Execution payload header of the Deneb and Electra forks.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct ExecutionPayloadHeaderDeneb {
+    pub parent_hash: Hash32,
+    pub fee_recipient: Address,
+    pub state_root: Hash32,
+    pub receipts_root: Hash32,
+    pub logs_bloom: Bloom,
+    pub prev_randao: Hash32,
+    #[serde(with = "quoted_u64")]
+    pub block_number: u64,
+    #[serde(with = "quoted_u64")]
+    pub gas_limit: u64,
+    #[serde(with = "quoted_u64")]
+    pub gas_used: u64,
+    #[serde(with = "quoted_u64")]
+    pub timestamp: u64,
+    pub extra_data: Bytes,
+    #[serde(with = "quoted_u256")]
+    pub base_fee_per_gas: U256,
+    pub block_hash: Hash32,
+    pub transactions_root: Hash32,
+    pub withdrawals_root: Hash32,
+    #[serde(with = "quoted_u64")]
+    pub blob_gas_used: u64,
+    #[serde(with = "quoted_u64")]
+    pub excess_blob_gas: u64,
+}
+
+/// Deposit request included in an Electra execution payload.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub struct DepositRequest {
+    pub pubkey: BlsPublicKey,
+    pub withdrawal_credentials: Hash32,
+    #[serde(with = "quoted_u64")]
+    pub amount: u64,
+    pub signature: BlsSignature,
+    #[serde(with = "quoted_u64")]
+    pub index: u64,
+}
+
+/// Withdrawal request included in an Electra execution payload.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub struct WithdrawalRequest {
+    pub source_address: Address,
+    pub validator_pubkey: BlsPublicKey,
+    #[serde(with = "quoted_u64")]
+    pub amount: u64,
+}
+
+/// Consolidation request included in an Electra execution payload.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub struct ConsolidationRequest {
+    pub source_address: Address,
+    pub source_pubkey: BlsPublicKey,
+    pub target_pubkey: BlsPublicKey,
+}
+
+/// Execution layer requests of the Electra fork.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct ExecutionRequests {
+    pub deposits: Vec<DepositRequest>,
+    pub withdrawals: Vec<WithdrawalRequest>,
+    pub consolidations: Vec<ConsolidationRequest>,
+}
+
+/// Builder bid of the Capella fork.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub struct BuilderBidCapella {
+    pub header: ExecutionPayloadHeaderCapella,
+    pub value: Wei,
+    pub pubkey: BlsPublicKey,
+}
+
+/// Builder bid of the Deneb fork.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub struct BuilderBidDeneb {
+    pub header: ExecutionPayloadHeaderDeneb,
+    pub blob_kzg_commitments: Vec<KzgCommitment>,
+    pub value: Wei,
+    pub pubkey: BlsPublicKey,
+}
+
+/// Builder bid of the Electra fork.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub struct BuilderBidElectra {
+    pub header: ExecutionPayloadHeaderDeneb,
+    pub blob_kzg_commitments: Vec<KzgCommitment>,
+    pub execution_requests: ExecutionRequests,
+    pub value: Wei,
+    pub pubkey: BlsPublicKey,
+}
+
+/// Signed builder bid returned by the getHeader endpoint, tagged with its fork.
+///
+/// [Visit the docs](https://ethereum.github.io/builder-specs/#/Builder/getHeader) for more info.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "version", content = "data", rename_all = "lowercase")]
+pub enum SignedBuilderBid {
+    /// A Capella builder bid.
+    Capella(Signed<BuilderBidCapella>),
+    /// A Deneb builder bid.
+    Deneb(Signed<BuilderBidDeneb>),
+    /// An Electra builder bid.
+    Electra(Signed<BuilderBidElectra>),
+}
+
+impl SignedBuilderBid {
+    /// Returns the fork of the bid.
+    pub fn fork(&self) -> Fork {
+        match self {
+            Self::Capella(_) => Fork::Capella,
+            Self::Deneb(_) => Fork::Deneb,
+            Self::Electra(_) => Fork::Electra,
+        }
+    }
+
+    /// Returns the value of the bid paid to the proposer.
+    pub fn value(&self) -> Wei {
+        match self {
+            Self::Capella(bid) => bid.message.value,
+            Self::Deneb(bid) => bid.message.value,
+            Self::Electra(bid) => bid.message.value,
+        }
+    }
+
+    /// Returns the public key the bid was signed with.
+    pub fn pubkey(&self) -> &BlsPublicKey {
+        match self {
+            Self::Capella(bid) => &bid.message.pubkey,
+            Self::Deneb(bid) => &bid.message.pubkey,
+            Self::Electra(bid) => &bid.message.pubkey,
+        }
+    }
+
+    /// Returns the signature of the bid.
+    pub fn signature(&self) -> &BlsSignature {
+        match self {
+            Self::Capella(bid) => &bid.signature,
+            Self::Deneb(bid) => &bid.signature,
+            Self::Electra(bid) => &bid.signature,
+        }
+    }
+
+    /// Returns the hash of the block the bid is for.
+    pub fn block_hash(&self) -> &Hash32 {
+        match self {
+            Self::Capella(bid) => &bid.message.header.block_hash,
+            Self::Deneb(bid) => &bid.message.header.block_hash,
+            Self::Electra(bid) => &bid.message.header.block_hash,
+        }
+    }
+
+    /// Returns the hash of the parent of the block the bid is for.
+    pub fn parent_hash(&self) -> &Hash32 {
+        match self {
+            Self::Capella(bid) => &bid.message.header.parent_hash,
+            Self::Deneb(bid) => &bid.message.header.parent_hash,
+            Self::Electra(bid) => &bid.message.header.parent_hash,
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::{Fork, SignedBuilderBid};
+    use crate::primitives::Wei;
+
+    /// Signed Deneb builder bid, as returned by the getHeader endpoint.
+    pub(crate) const SIGNED_BUILDER_BID_DENEB: &str = r#"{
+        "version": "deneb",
+        "data": {
+            "message": {
+                "header": {
+                    "parent_hash": "0xcf8e0d4e9587369b2301d0790347320302cc0943d5a1884560367e8208d920f2",
+                    "fee_recipient": "0xabcf8e0d4e9587369b2301d0790347320302cc09",
+                    "state_root": "0xcf8e0d4e9587369b2301d0790347320302cc0943d5a1884560367e8208d920f2",
+                    "receipts_root": "0xcf8e0d4e9587369b2301d0790347320302cc0943d5a1884560367e8208d920f2",
+                    "logs_bloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                    "prev_randao": "0xcf8e0d4e9587369b2301d0790347320302cc0943d5a1884560367e8208d920f2",
+                    "block_number": "1",
+                    "gas_limit": "30000000",
+                    "gas_used": "15000000",
+                    "timestamp": "1700000000",
+                    "extra_data": "0x6265617665726275696c642e6f7267",
+                    "base_fee_per_gas": "7",
+                    "block_hash": "0x1f8e0d4e9587369b2301d0790347320302cc0943d5a1884560367e8208d920f2",
+                    "transactions_root": "0xcf8e0d4e9587369b2301d0790347320302cc0943d5a1884560367e8208d920f2",
+                    "withdrawals_root": "0xcf8e0d4e9587369b2301d0790347320302cc0943d5a1884560367e8208d920f2",
+                    "blob_gas_used": "131072",
+                    "excess_blob_gas": "0"
+                },
+                "blob_kzg_commitments": [
+                    "0xa94170080872584e54a1cf092d845703b13907f2e6b3b1c0ad573b910530499e3bcd48c6378846b80d2bfa58c81cf3d5"
+                ],
+                "value": "52345678901234567",
+                "pubkey": "0x93247f2209abcacf57b75a51dafae777f9dd38bc7053d1af526f220a7489a6d3a2753e5f3e8b1cfe39b56f43611df74a"
+            },
+            "signature": "0x1b66ac1fb663c9bc59509846d6ec05345bd908eda73e670af888da41af171505cc411d61252fb6cb3fa0017b679f8bb2305b26a285fa2737f175668d0dff91cc1b66ac1fb663c9bc59509846d6ec05345bd908eda73e670af888da41af171505"
+        }
+    }"#;
+
+    #[test]
+    fn test_signed_builder_bid_deneb() {
+        let bid: SignedBuilderBid = serde_json::from_str(SIGNED_BUILDER_BID_DENEB).unwrap();
+
+        assert_eq!(bid.fork(), Fork::Deneb);
+        assert_eq!(bid.value(), Wei::from(52_345_678_901_234_567u64));
+        assert_eq!(
+            bid.block_hash().to_string(),
+            "0x1f8e0d4e9587369b2301d0790347320302cc0943d5a1884560367e8208d920f2"
+        );
+        let SignedBuilderBid::Deneb(deneb) = &bid else {
+            unreachable!()
+        };
+        assert_eq!(deneb.message.header.blob_gas_used, 131072);
+        assert_eq!(deneb.message.blob_kzg_commitments.len(), 1);
+    }
+
+    #[test]
+    fn test_signed_builder_bid_electra() {
+        // Electra bids use the Deneb header, with the execution requests added to the bid.
+        let json = SIGNED_BUILDER_BID_DENEB
+            .replace(r#""version": "deneb""#, r#""version": "electra""#)
+            .replace(
+                r#""value":"#,
+                r#""execution_requests": {
+                    "deposits": [],
+                    "withdrawals": [{
+                        "source_address": "0xabcf8e0d4e9587369b2301d0790347320302cc09",
+                        "validator_pubkey": "0x93247f2209abcacf57b75a51dafae777f9dd38bc7053d1af526f220a7489a6d3a2753e5f3e8b1cfe39b56f43611df74a",
+                        "amount": "1"
+                    }],
+                    "consolidations": []
+                },
+                "value":"#,
+            );
+        let bid: SignedBuilderBid = serde_json::from_str(&json).unwrap();
+
+        let SignedBuilderBid::Electra(electra) = &bid else {
+            panic!("expected an electra bid, got {}", bid.fork());
+        };
+        assert_eq!(electra.message.execution_requests.withdrawals.len(), 1);
+
+        // Electra bids without execution requests are rejected.
+        let json = SIGNED_BUILDER_BID_DENEB.replace("deneb", "electra");
+        assert!(serde_json::from_str::<SignedBuilderBid>(&json).is_err());
+    }
+}
@@ -0,0 +1,421 @@
+use alloy_primitives::FixedBytes;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    ExecutionPayloadHeaderCapella, ExecutionPayloadHeaderDeneb, ExecutionRequests, Fork, Signed,
+};
+use crate::{
+    primitives::{
+        quoted_u256, quoted_u64, quoted_u64_vec, Address, Bloom, BlsPublicKey, BlsSignature, Bytes,
+        Hash32, KzgCommitment, KzgProof, U256,
+    },
+    ssz, Error, Result,
+};
+
+/// Beacon block, generic over its body.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct BeaconBlock<B> {
+    #[serde(with = "quoted_u64")]
+    pub slot: u64,
+    #[serde(with = "quoted_u64")]
+    pub proposer_index: u64,
+    pub parent_root: Hash32,
+    pub state_root: Hash32,
+    pub body: B,
+}
+
+/// Header of a beacon block.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct BeaconBlockHeader {
+    #[serde(with = "quoted_u64")]
+    pub slot: u64,
+    #[serde(with = "quoted_u64")]
+    pub proposer_index: u64,
+    pub parent_root: Hash32,
+    pub state_root: Hash32,
+    pub body_root: Hash32,
+}
+
+/// Eth1 data vote of a beacon block.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct Eth1Data {
+    pub deposit_root: Hash32,
+    #[serde(with = "quoted_u64")]
+    pub deposit_count: u64,
+    pub block_hash: Hash32,
+}
+
+/// Proof that a proposer signed two different blocks for the same slot.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct ProposerSlashing {
+    pub signed_header_1: Signed<BeaconBlockHeader>,
+    pub signed_header_2: Signed<BeaconBlockHeader>,
+}
+
+/// Epoch boundary checkpoint.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct Checkpoint {
+    #[serde(with = "quoted_u64")]
+    pub epoch: u64,
+    pub root: Hash32,
+}
+
+/// Data attested to by validators.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct AttestationData {
+    #[serde(with = "quoted_u64")]
+    pub slot: u64,
+    #[serde(with = "quoted_u64")]
+    pub index: u64,
+    pub beacon_block_root: Hash32,
+    pub source: Checkpoint,
+    pub target: Checkpoint,
+}
+
+/// Attestation with the indices of the attesting validators.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct IndexedAttestation {
+    #[serde(with = "quoted_u64_vec")]
+    pub attesting_indices: Vec<u64>,
+    pub data: AttestationData,
+    pub signature: BlsSignature,
+}
+
+/// Proof that validators made conflicting attestations.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct AttesterSlashing {
+    pub attestation_1: IndexedAttestation,
+    pub attestation_2: IndexedAttestation,
+}
+
+/// Aggregated attestation, before the Electra fork.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct Attestation {
+    pub aggregation_bits: Bytes,
+    pub data: AttestationData,
+    pub signature: BlsSignature,
+}
+
+/// Aggregated attestation of the Electra fork, spanning multiple committees.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct AttestationElectra {
+    pub aggregation_bits: Bytes,
+    pub data: AttestationData,
+    pub signature: BlsSignature,
+    pub committee_bits: Bytes,
+}
+
+/// Data of a deposit to the deposit contract.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct DepositData {
+    pub pubkey: BlsPublicKey,
+    pub withdrawal_credentials: Hash32,
+    #[serde(with = "quoted_u64")]
+    pub amount: u64,
+    pub signature: BlsSignature,
+}
+
+/// Deposit with its Merkle proof.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct Deposit {
+    pub proof: Vec<Hash32>,
+    pub data: DepositData,
+}
+
+/// Voluntary exit of a validator.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct VoluntaryExit {
+    #[serde(with = "quoted_u64")]
+    pub epoch: u64,
+    #[serde(with = "quoted_u64")]
+    pub validator_index: u64,
+}
+
+/// Sync committee signature aggregate.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct SyncAggregate {
+    pub sync_committee_bits: FixedBytes<64>,
+    pub sync_committee_signature: BlsSignature,
+}
+
+/// Change of the withdrawal credentials of a validator to an execution address.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct BlsToExecutionChange {
+    #[serde(with = "quoted_u64")]
+    pub validator_index: u64,
+    pub from_bls_pubkey: BlsPublicKey,
+    pub to_execution_address: Address,
+}
+
+/// Body of a blinded beacon block of the Capella fork.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct BlindedBeaconBlockBodyCapella {
+    pub randao_reveal: BlsSignature,
+    pub eth1_data: Eth1Data,
+    pub graffiti: Hash32,
+    pub proposer_slashings: Vec<ProposerSlashing>,
+    pub attester_slashings: Vec<AttesterSlashing>,
+    pub attestations: Vec<Attestation>,
+    pub deposits: Vec<Deposit>,
+    pub voluntary_exits: Vec<Signed<VoluntaryExit>>,
+    pub sync_aggregate: SyncAggregate,
+    pub execution_payload_header: ExecutionPayloadHeaderCapella,
+    pub bls_to_execution_changes: Vec<Signed<BlsToExecutionChange>>,
+}
+
+/// Body of a blinded beacon block of the Deneb fork.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct BlindedBeaconBlockBodyDeneb {
+    pub randao_reveal: BlsSignature,
+    pub eth1_data: Eth1Data,
+    pub graffiti: Hash32,
+    pub proposer_slashings: Vec<ProposerSlashing>,
+    pub attester_slashings: Vec<AttesterSlashing>,
+    pub attestations: Vec<Attestation>,
+    pub deposits: Vec<Deposit>,
+    pub voluntary_exits: Vec<Signed<VoluntaryExit>>,
+    pub sync_aggregate: SyncAggregate,
+    pub execution_payload_header: ExecutionPayloadHeaderDeneb,
+    pub bls_to_execution_changes: Vec<Signed<BlsToExecutionChange>>,
+    pub blob_kzg_commitments: Vec<KzgCommitment>,
+}
+
+/// Body of a blinded beacon block of the Electra fork.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct BlindedBeaconBlockBodyElectra {
+    pub randao_reveal: BlsSignature,
+    pub eth1_data: Eth1Data,
+    pub graffiti: Hash32,
+    pub proposer_slashings: Vec<ProposerSlashing>,
+    pub attester_slashings: Vec<AttesterSlashing>,
+    pub attestations: Vec<AttestationElectra>,
+    pub deposits: Vec<Deposit>,
+    pub voluntary_exits: Vec<Signed<VoluntaryExit>>,
+    pub sync_aggregate: SyncAggregate,
+    pub execution_payload_header: ExecutionPayloadHeaderDeneb,
+    pub bls_to_execution_changes: Vec<Signed<BlsToExecutionChange>>,
+    pub blob_kzg_commitments: Vec<KzgCommitment>,
+    pub execution_requests: ExecutionRequests,
+}
+
+/// Signed blinded beacon block submitted by a proposer to get the payload of the winning bid.
+///
+/// Serialized as the signed block alone; its fork is sent in the `Eth-Consensus-Version` header.
+///
+/// [Visit the docs](https://ethereum.github.io/builder-specs/#/Builder/submitBlindedBlock) for more info.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum SignedBlindedBeaconBlock {
+    /// A Capella blinded block.
+    Capella(Signed<BeaconBlock<BlindedBeaconBlockBodyCapella>>),
+    /// A Deneb blinded block.
+    Deneb(Signed<BeaconBlock<BlindedBeaconBlockBodyDeneb>>),
+    /// An Electra blinded block.
+    Electra(Signed<BeaconBlock<BlindedBeaconBlockBodyElectra>>),
+}
+
+impl SignedBlindedBeaconBlock {
+    /// Returns the fork of the block.
+    pub fn fork(&self) -> Fork {
+        match self {
+            Self::Capella(_) => Fork::Capella,
+            Self::Deneb(_) => Fork::Deneb,
+            Self::Electra(_) => Fork::Electra,
+        }
+    }
+
+    /// Returns the slot of the block.
+    pub fn slot(&self) -> u64 {
+        match self {
+            Self::Capella(block) => block.message.slot,
+            Self::Deneb(block) => block.message.slot,
+            Self::Electra(block) => block.message.slot,
+        }
+    }
+
+    /// Returns the hash of the execution block the blinded block commits to.
+    pub fn block_hash(&self) -> &Hash32 {
+        match self {
+            Self::Capella(block) => &block.message.body.execution_payload_header.block_hash,
+            Self::Deneb(block) => &block.message.body.execution_payload_header.block_hash,
+            Self::Electra(block) => &block.message.body.execution_payload_header.block_hash,
+        }
+    }
+}
+
+/// Withdrawal from the consensus layer, included in an execution payload.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct Withdrawal {
+    #[serde(with = "quoted_u64")]
+    pub index: u64,
+    #[serde(with = "quoted_u64")]
+    pub validator_index: u64,
+    pub address: Address,
+    #[serde(with = "quoted_u64")]
+    pub amount: u64,
+}
+
+/// Execution payload of the Capella fork.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct ExecutionPayloadCapella {
+    pub parent_hash: Hash32,
+    pub fee_recipient: Address,
+    pub state_root: Hash32,
+    pub receipts_root: Hash32,
+    pub logs_bloom: Bloom,
+    pub prev_randao: Hash32,
+    #[serde(with = "quoted_u64")]
+    pub block_number: u64,
+    #[serde(with = "quoted_u64")]
+    pub gas_limit: u64,
+    #[serde(with = "quoted_u64")]
+    pub gas_used: u64,
+    #[serde(with = "quoted_u64")]
+    pub timestamp: u64,
+    pub extra_data: Bytes,
+    #[serde(with = "quoted_u256")]
+    pub base_fee_per_gas: U256,
+    pub block_hash: Hash32,
+    pub transactions: Vec<Bytes>,
+    pub withdrawals: Vec<Withdrawal>,
+}
+
+/// Execution payload of the Deneb and Electra forks.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct ExecutionPayloadDeneb {
+    pub parent_hash: Hash32,
+    pub fee_recipient: Address,
+    pub state_root: Hash32,
+    pub receipts_root: Hash32,
+    pub logs_bloom: Bloom,
+    pub prev_randao: Hash32,
+    #[serde(with = "quoted_u64")]
+    pub block_number: u64,
+    #[serde(with = "quoted_u64")]
+    pub gas_limit: u64,
+    #[serde(with = "quoted_u64")]
+    pub gas_used: u64,
+    #[serde(with = "quoted_u64")]
+    pub timestamp: u64,
+    pub extra_data: Bytes,
+    #[serde(with = "quoted_u256")]
+    pub base_fee_per_gas: U256,
+    pub block_hash: Hash32,
+    pub transactions: Vec<Bytes>,
+    pub withdrawals: Vec<Withdrawal>,
+    #[serde(with = "quoted_u64")]
+    pub blob_gas_used: u64,
+    #[serde(with = "quoted_u64")]
+    pub excess_blob_gas: u64,
+}
+
+/// Blobs of a block, with their KZG commitments and proofs.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct BlobsBundle {
+    pub commitments: Vec<KzgCommitment>,
+    pub proofs: Vec<KzgProof>,
+    pub blobs: Vec<Bytes>,
+}
+
+impl BlobsBundle {
+    /// Check that every blob is 131072 bytes long and has exactly one commitment and one proof.
+    ///
+    /// Fails with [`Error::Serialize`] otherwise, as relays reject such bundles.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |reason: String| Error::Serialize(serde::ser::Error::custom(reason));
+
+        if self.commitments.len() != self.blobs.len() || self.proofs.len() != self.blobs.len() {
+            return Err(invalid(format!(
+                "blobs bundle has {} blobs, {} commitments and {} proofs",
+                self.blobs.len(),
+                self.commitments.len(),
+                self.proofs.len()
+            )));
+        }
+        if let Some((index, blob)) = self
+            .blobs
+            .iter()
+            .enumerate()
+            .find(|(_, blob)| blob.len() != ssz::BYTES_PER_BLOB)
+        {
+            return Err(invalid(format!(
+                "blob {} is {} bytes long instead of {}",
+                index,
+                blob.len(),
+                ssz::BYTES_PER_BLOB
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Execution payload with the blobs of the block, returned from the Deneb fork onwards.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct ExecutionPayloadAndBlobsBundle {
+    pub execution_payload: ExecutionPayloadDeneb,
+    pub blobs_bundle: BlobsBundle,
+}
+
+/// Unblinded payload returned by the submitBlindedBlock endpoint, tagged with its fork.
+///
+/// [Visit the docs](https://ethereum.github.io/builder-specs/#/Builder/submitBlindedBlock) for more info.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "version", content = "data", rename_all = "lowercase")]
+pub enum GetPayloadResponse {
+    /// A Capella execution payload.
+    Capella(ExecutionPayloadCapella),
+    /// A Deneb execution payload and blobs bundle.
+    Deneb(ExecutionPayloadAndBlobsBundle),
+    /// An Electra execution payload and blobs bundle.
+    Electra(ExecutionPayloadAndBlobsBundle),
+}
+
+impl GetPayloadResponse {
+    /// Returns the fork of the payload.
+    pub fn fork(&self) -> Fork {
+        match self {
+            Self::Capella(_) => Fork::Capella,
+            Self::Deneb(_) => Fork::Deneb,
+            Self::Electra(_) => Fork::Electra,
+        }
+    }
+
+    /// Returns the hash of the execution block.
+    pub fn block_hash(&self) -> &Hash32 {
+        match self {
+            Self::Capella(payload) => &payload.block_hash,
+            Self::Deneb(payload) | Self::Electra(payload) => &payload.execution_payload.block_hash,
+        }
+    }
+
+    /// Returns the blobs bundle of the payload, if any.
+    pub fn blobs_bundle(&self) -> Option<&BlobsBundle> {
+        match self {
+            Self::Capella(_) => None,
+            Self::Deneb(payload) | Self::Electra(payload) => Some(&payload.blobs_bundle),
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
+
+use crate::{primitives::ForkVersion, RateLimit};
 
 lazy_static! {
     /// Default mevboost relays to use for queries.
@@ -13,8 +15,68 @@ lazy_static! {
         m.insert("bloxroute-regulated", "https://0xb0b07cd0abef743db4260b0ed50619cf6ad4d82064cb4fbec9d3ec530f7c5e6793d9f286c4e082c0244ffb9f2658fe88@bloxroute.regulated.blxrbdn.com");
         m
     };
+
+    /// Relays of the Holesky testnet, used by [`Network::Holesky`](crate::Network::Holesky).
+    pub static ref HOLESKY_RELAYS: HashMap<&'static str, &'static str> = {
+        let mut m = HashMap::new();
+        m.insert("flashbots", "https://0xafa4c6985aa049fb79dd37010438cfebeb0f2bd42b115b89dd678dab0670c1de38da0c4e9138c9290a398ecd9a0b3110@boost-relay-holesky.flashbots.net");
+        m.insert("ultrasound", "https://0xb1559beef7b5ba3127485bbbb090362d9f497ba64e177ee2c8e7db74746306efad687f2cf8574e38d70067d40ef136dc@relay-stag.ultrasound.money");
+        m.insert("aestus", "https://0xab78bf8c781c58078c3beb5710c57940874dd96aef2835e7742c866b4c7c0406754376c2c8285a36c630346aa5c5f833@holesky.aestus.live");
+        m.insert("titan", "https://0xaa58208899c6105603b74396734a6263cc7d947f444f396a90f7b7d3e65d102aec7e5e5291b27e08d02c50a050825c2f@holesky.titanrelay.xyz");
+        m
+    };
+
+    /// Relays of the Sepolia testnet, used by [`Network::Sepolia`](crate::Network::Sepolia).
+    pub static ref SEPOLIA_RELAYS: HashMap<&'static str, &'static str> = {
+        let mut m = HashMap::new();
+        m.insert("flashbots", "https://0x845bd072b7cd566f02faeb0a4033ce9399e42839ced64e8b2adcfc859ed1e8e1a5a293336a49feac6d9a5edb779be53a@boost-relay-sepolia.flashbots.net");
+        m
+    };
+
+    /// Relays of the Hoodi testnet, used by [`Network::Hoodi`](crate::Network::Hoodi).
+    pub static ref HOODI_RELAYS: HashMap<&'static str, &'static str> = {
+        let mut m = HashMap::new();
+        m.insert("flashbots", "https://0xafa4c6985aa049fb79dd37010438cfebeb0f2bd42b115b89dd678dab0670c1de38da0c4e9138c9290a398ecd9a0b3110@boost-relay-hoodi.flashbots.net");
+        m.insert("aestus", "https://0x98f0ef62f00780cf8eb06701a7d22725b9437d4768bb19b363e882ae87129945ec206ec2dc16933f31d983f8225772b6@hoodi.aestus.live");
+        m.insert("titan", "https://0xaa58208899c6105603b74396734a6263cc7d947f444f396a90f7b7d3e65d102aec7e5e5291b27e08d02c50a050825c2f@hoodi.titanrelay.xyz");
+        m
+    };
+
+    /// Default client-side rate limits for the relays in [`DEFAULT_RELAYS`].
+    ///
+    /// Public relays enforce rate limits on their data APIs and may temporarily ban clients
+    /// that exceed them, so these defaults are conservative. Override them with
+    /// [`crate::Client::with_rate_limit`] or [`crate::ClientBuilder::rate_limit`].
+    pub static ref DEFAULT_RATE_LIMITS: HashMap<&'static str, RateLimit> =
+        default_rate_limits(&DEFAULT_RELAYS);
+
+    /// Default client-side rate limits for the relays in [`HOLESKY_RELAYS`].
+    pub static ref HOLESKY_RATE_LIMITS: HashMap<&'static str, RateLimit> =
+        default_rate_limits(&HOLESKY_RELAYS);
+
+    /// Default client-side rate limits for the relays in [`SEPOLIA_RELAYS`].
+    pub static ref SEPOLIA_RATE_LIMITS: HashMap<&'static str, RateLimit> =
+        default_rate_limits(&SEPOLIA_RELAYS);
+
+    /// Default client-side rate limits for the relays in [`HOODI_RELAYS`].
+    pub static ref HOODI_RATE_LIMITS: HashMap<&'static str, RateLimit> =
+        default_rate_limits(&HOODI_RELAYS);
+}
+
+/// Helper function to limit every relay of a network to [`DEFAULT_RELAY_RATE_LIMIT`].
+fn default_rate_limits(
+    relays: &HashMap<&'static str, &'static str>,
+) -> HashMap<&'static str, RateLimit> {
+    relays
+        .keys()
+        .map(|name| (*name, DEFAULT_RELAY_RATE_LIMIT))
+        .collect()
 }
 
+/// Rate limit of each relay in the default rate limits of the built-in networks
+/// ([`DEFAULT_RATE_LIMITS`] for mainnet): 2 requests per second with a burst of 5.
+pub static DEFAULT_RELAY_RATE_LIMIT: RateLimit = RateLimit::new_unchecked(2.0, 5);
+
 /// Relay endpoint for getting a list of validator registrations
 /// for validators scheduled to propose in the current and next epoch.
 ///
@@ -35,3 +97,89 @@ pub static GET_DELIVERED_PAYLOADS: &str = "/relay/v1/data/bidtraces/proposer_pay
 ///
 /// [Visit the docs](https://flashbots.github.io/relay-specs/#/Data/getReceivedBids) for more info.
 pub static GET_BUILDER_BLOCKS_RECEIVED: &str = "/relay/v1/data/bidtraces/builder_blocks_received";
+
+/// Builder API endpoint for getting the best bid of a relay for a slot,
+/// followed by `/{slot}/{parent_hash}/{pubkey}`.
+///
+/// [Visit the docs](https://ethereum.github.io/builder-specs/#/Builder/getHeader) for more info.
+pub static GET_HEADER: &str = "/eth/v1/builder/header";
+
+/// Builder API endpoint for submitting a signed blinded block and getting its execution payload.
+///
+/// [Visit the docs](https://ethereum.github.io/builder-specs/#/Builder/submitBlindedBlock) for more info.
+pub static SUBMIT_BLINDED_BLOCK: &str = "/eth/v1/builder/blinded_blocks";
+
+/// Endpoint to submit a block built by a builder.
+///
+/// [Visit the docs](https://flashbots.github.io/relay-specs/#/Builder/submitBlock) for more info.
+pub static SUBMIT_BLOCK: &str = "/relay/v1/builder/blocks";
+
+/// Header carrying the consensus fork of request and response bodies.
+pub static CONSENSUS_VERSION_HEADER: &str = "Eth-Consensus-Version";
+
+/// Builder API endpoint for registering validators with a relay.
+///
+/// [Visit the docs](https://ethereum.github.io/builder-specs/#/Builder/registerValidator) for more info.
+pub static REGISTER_VALIDATORS: &str = "/eth/v1/builder/validators";
+
+/// Builder API endpoint for checking that a relay is ready to serve requests.
+///
+/// [Visit the docs](https://ethereum.github.io/builder-specs/#/Builder/status) for more info.
+pub static GET_STATUS: &str = "/eth/v1/builder/status";
+
+/// Default maximum number of relays queried concurrently by multi-relay methods.
+pub static DEFAULT_MAX_CONCURRENCY: usize = 8;
+
+/// Default timeout for a single relay query in multi-relay methods.
+pub static DEFAULT_RELAY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default total timeout of a single HTTP request.
+pub static DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default timeout for the connect phase of a single HTTP request.
+pub static DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default value of the `User-Agent` header sent with every request.
+pub static DEFAULT_USER_AGENT: &str = concat!("mevboost-relay-api/", env!("CARGO_PKG_VERSION"));
+
+/// Maximum number of payloads returned by a single delivered payloads query,
+/// used as the page size when streaming delivered payloads.
+pub static PAYLOAD_DELIVERED_PAGE_LIMIT: u64 = 200;
+
+/// Genesis fork version of Ethereum mainnet, used to compute the builder signing domain.
+pub static MAINNET_GENESIS_FORK_VERSION: ForkVersion = ForkVersion::ZERO;
+
+/// Genesis fork version of the Holesky testnet.
+pub static HOLESKY_GENESIS_FORK_VERSION: ForkVersion = ForkVersion::new([0x01, 0x01, 0x70, 0x00]);
+
+/// Genesis fork version of the Sepolia testnet.
+pub static SEPOLIA_GENESIS_FORK_VERSION: ForkVersion = ForkVersion::new([0x90, 0x00, 0x00, 0x69]);
+
+/// Genesis fork version of the Hoodi testnet.
+pub static HOODI_GENESIS_FORK_VERSION: ForkVersion = ForkVersion::new([0x10, 0x00, 0x09, 0x10]);
+
+/// Genesis time of Ethereum mainnet, in seconds since the Unix epoch.
+pub static MAINNET_GENESIS_TIME: u64 = 1_606_824_023;
+
+/// Genesis time of the Holesky testnet, in seconds since the Unix epoch.
+pub static HOLESKY_GENESIS_TIME: u64 = 1_695_902_400;
+
+/// Genesis time of the Sepolia testnet, in seconds since the Unix epoch.
+pub static SEPOLIA_GENESIS_TIME: u64 = 1_655_733_600;
+
+/// Genesis time of the Hoodi testnet, in seconds since the Unix epoch.
+pub static HOODI_GENESIS_TIME: u64 = 1_742_213_400;
+
+/// Number of slots per epoch of the built-in networks.
+pub static SLOTS_PER_EPOCH: u64 = 32;
+
+/// Duration of a slot of the built-in networks, in seconds.
+pub static SECONDS_PER_SLOT: u64 = 12;
+
+/// Domain type of the messages signed for the builder API.
+///
+/// [Visit the docs](https://ethereum.github.io/builder-specs/#/Builder/registerValidator) for more info.
+pub static DOMAIN_APPLICATION_BUILDER: [u8; 4] = [0x00, 0x00, 0x00, 0x01];
+
+/// Domain separation tag of the BLS signatures of the consensus layer.
+pub static BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
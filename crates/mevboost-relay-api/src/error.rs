@@ -0,0 +1,134 @@
+use crate::types::RelayErrorResponse;
+
+/// Result type returned by the relay API [`Client`](crate::Client).
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Errors that can occur while querying a relay.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The requested relay name is not in the client's list of relays.
+    #[error("relay `{0}` not found in list of relays")]
+    UnknownRelay(String),
+
+    /// The network name does not match any built-in network.
+    #[error("unknown network `{0}`")]
+    UnknownNetwork(String),
+
+    /// The chain spec could not be loaded.
+    #[error("invalid chain spec: {0}")]
+    InvalidChainSpec(String),
+
+    /// The relay URL is malformed.
+    #[error("invalid relay URL `{url}`: {reason}")]
+    InvalidRelayUrl {
+        /// The malformed URL.
+        url: String,
+        /// Why the URL was rejected.
+        reason: String,
+    },
+
+    /// The rate limit is invalid.
+    #[error("invalid rate limit: {0}")]
+    InvalidRateLimit(String),
+
+    /// The query options are invalid. Returned before any request is sent.
+    #[error("invalid query: {0}")]
+    InvalidQuery(String),
+
+    /// The request body could not be serialized.
+    #[error("failed to serialize request body: {0}")]
+    Serialize(#[source] serde_json::Error),
+
+    /// The HTTP request could not be sent or the response could not be read.
+    #[error("HTTP transport error: {0}")]
+    Transport(#[from] reqwest::Error),
+
+    /// The relay did not answer within the configured timeout.
+    #[error("relay request timed out after {0:?}")]
+    Timeout(std::time::Duration),
+
+    /// The relay responded with a non-success HTTP status code.
+    #[error("relay responded with HTTP status {status}: {body}")]
+    Status {
+        /// The HTTP status code returned by the relay.
+        status: reqwest::StatusCode,
+        /// The raw response body.
+        body: String,
+    },
+
+    /// The relay responded with a non-success HTTP status code and
+    /// an error object as defined in the relay specs.
+    #[error("relay responded with HTTP status {status} (code {}): {}", .error.code, .error.message)]
+    Relay {
+        /// The HTTP status code returned by the relay.
+        status: reqwest::StatusCode,
+        /// The decoded error object.
+        error: RelayErrorResponse,
+    },
+
+    /// The validator is not registered with the queried relay.
+    #[error("validator {pubkey} not registered with relay `{relay}`")]
+    ValidatorNotRegistered {
+        /// The name of the queried relay.
+        relay: String,
+        /// The public key of the validator.
+        pubkey: String,
+    },
+
+    /// A validator registration is not signed by its validator.
+    #[error("invalid registration of validator {pubkey}: {reason}")]
+    InvalidRegistration {
+        /// The public key of the validator.
+        pubkey: String,
+        /// Why the registration was rejected.
+        reason: String,
+    },
+
+    /// A builder bid is not signed by the relay that returned it.
+    #[error("invalid bid signature for relay {pubkey}: {reason}")]
+    InvalidBidSignature {
+        /// The public key of the relay.
+        pubkey: String,
+        /// Why the bid was rejected.
+        reason: String,
+    },
+
+    /// A list is longer than the maximum length of its SSZ type, so it has no hash tree root.
+    #[error("SSZ list of {len} items exceeds its maximum length of {max_len}")]
+    SszListTooLong {
+        /// The length of the list.
+        len: usize,
+        /// The maximum length of the list.
+        max_len: usize,
+    },
+
+    /// The payload returned by a relay does not match the submitted blinded block.
+    #[error("relay `{relay}` returned a payload not matching the submitted block: {reason}")]
+    PayloadMismatch {
+        /// The name of the queried relay.
+        relay: String,
+        /// How the payload differs from the block.
+        reason: String,
+    },
+
+    /// The response body could not be deserialized into the expected type.
+    #[error("failed to parse JSON response: {source}")]
+    Deserialize {
+        /// The underlying JSON error.
+        source: serde_json::Error,
+        /// The raw response body.
+        body: String,
+    },
+}
+
+impl Error {
+    /// Returns the HTTP status code returned by the relay, if the error was caused
+    /// by a non-success response.
+    pub fn status(&self) -> Option<reqwest::StatusCode> {
+        match self {
+            Self::Status { status, .. } | Self::Relay { status, .. } => Some(*status),
+            Self::Transport(e) => e.status(),
+            _ => None,
+        }
+    }
+}
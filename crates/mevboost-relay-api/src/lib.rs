@@ -11,43 +11,245 @@
 #[macro_use]
 extern crate lazy_static;
 
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    future::Future,
+    io::Write,
+    time::{Duration, Instant},
+};
+
+use flate2::{write::GzEncoder, Compression};
+use futures::stream::{self, StreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use tracing::Instrument;
+
+/// Re-export of the HTTP client library, to configure proxies and certificates on the [`ClientBuilder`].
+pub use reqwest;
 
 /// Constants used in the library.
 pub mod constants;
 
+/// Builder for configuring the client.
+pub mod builder;
+pub use builder::ClientBuilder;
+
+/// Error types returned by the client.
+pub mod error;
+pub use error::{Error, Result};
+
 /// Types used in the library.
 pub mod types;
 
+/// Consensus types of the builder API.
+pub mod consensus;
+
+/// Relay configuration.
+pub mod relay;
+pub use relay::{Relay, RelayOptions, RelayUrl};
+
+/// Built-in networks and their relay presets.
+pub mod network;
+pub use network::Network;
+
+/// Chain specs of the networks of the relays.
+pub mod spec;
+pub use spec::ChainSpec;
+
+/// Mapping of wall-clock time to slots and epochs.
+pub mod clock;
+pub use clock::SlotClock;
+
+/// Primitive types shared by the relay API types.
+pub mod primitives;
+use primitives::{BlsPublicKey, ForkVersion, Hash32};
+
+/// Retry policy for failed requests.
+pub mod retry;
+pub use retry::RetryPolicy;
+
+/// Client-side rate limiting of relay requests.
+pub mod rate_limit;
+pub use rate_limit::RateLimit;
+use rate_limit::RateLimiter;
+
+/// Minimal [SSZ](https://github.com/ethereum/consensus-specs/blob/dev/ssz/simple-serialize.md) encoding of the types sent to relays.
+pub(crate) mod ssz;
+pub use ssz::{Encode, TreeHash};
+
+/// BLS signing roots and signature verification of builder API messages.
+pub mod signing;
+
+/// Streams over paginated and slot-range relay queries.
+mod pagination;
+pub use pagination::BuilderBlocksStream;
+
+/// Outcomes of queries performed on multiple relays.
+pub mod response;
+pub use response::{MultiRelayResponse, MultiRelaySummary, RelayResponse};
+
 /// Mevboost relay API client.
 ///
 /// When created with [`Client::default()`], the client will use the default list of relays.
-/// These can be overridden in the library by using [`Client::with_relays()`] instead.
+/// These can be overridden in the library by using [`Client::with_relays()`] instead,
+/// and relays can be added or removed at runtime with [`Client::add_relay()`] and
+/// [`Client::remove_relay()`].
+/// Use [`Client::builder()`] or [`ClientBuilder`] to configure timeouts, proxies and other HTTP settings.
+///
+/// Methods that query all relays (`*_on_all_relays` and [`Client::query_all_relays()`])
+/// run concurrently, bounded by
+/// [`Client::with_max_concurrency()`], and each relay query is bounded by
+/// [`Client::with_relay_timeout()`].
 #[derive(Debug)]
-pub struct Client<'a> {
-    /// List of relay names and endpoints to use for queries.
-    relays: HashMap<&'a str, &'a str>,
+pub struct Client {
+    /// Relays to use for queries, by name.
+    pub(crate) relays: HashMap<String, Relay>,
     /// HTTP client used for requests.
-    inner: reqwest::Client,
+    pub(crate) inner: reqwest::Client,
+    /// Maximum number of relays queried concurrently by multi-relay methods.
+    pub(crate) max_concurrency: usize,
+    /// Timeout for a single relay query in multi-relay methods.
+    pub(crate) relay_timeout: Duration,
+    /// Policy for retrying failed requests.
+    pub(crate) retry_policy: RetryPolicy,
+    /// Policy for retrying failed block submissions and validator registrations.
+    pub(crate) submission_retry_policy: RetryPolicy,
+    /// Per-relay rate limiter applied to every request.
+    pub(crate) rate_limiter: RateLimiter,
+    /// Chain spec of the network of the relays, used to verify signatures and for epoch-based queries.
+    pub(crate) spec: ChainSpec,
+    /// Whether to verify the signatures of the validator registrations returned by relays.
+    pub(crate) verify_registrations: bool,
+    /// Whether to verify that the bids returned by relays are signed by the relay.
+    pub(crate) verify_bids: bool,
 }
 
-impl<'a> Default for Client<'a> {
+impl Default for Client {
     fn default() -> Self {
-        Self {
-            relays: constants::DEFAULT_RELAYS.clone(),
-            inner: reqwest::Client::new(),
-        }
+        ClientBuilder::default()
+            .build()
+            .expect("Failed to initialize HTTP client")
     }
 }
 
-impl<'a> Client<'a> {
+impl Client {
+    /// Create a [`ClientBuilder`] using the default list of relays.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
     /// Create a new MevBoost Relay API client with a custom list of relays.
     ///
-    /// Relays are a mapping of relay names to their endpoints.
-    /// See [`constants::DEFAULT_RELAYS`] for an example.
-    pub fn with_relays(relays: HashMap<&'a str, &'a str>) -> Self {
-        let inner = reqwest::Client::new();
-        Self { relays, inner }
+    /// See [`constants::DEFAULT_RELAYS`] for an example of relay URLs.
+    pub fn with_relays(relays: impl IntoIterator<Item = Relay>) -> Self {
+        ClientBuilder::with_relays(relays)
+            .build()
+            .expect("Failed to initialize HTTP client")
+    }
+
+    /// Create a new MevBoost Relay API client for the relays of the given network.
+    ///
+    /// The chain spec of the network is used for signature verification and epoch-based queries.
+    pub fn for_network(network: Network) -> Self {
+        ClientBuilder::for_network(network)
+            .build()
+            .expect("Failed to initialize HTTP client")
+    }
+
+    /// Set the maximum number of relays that are queried concurrently
+    /// by the `*_on_all_relays` methods. Values lower than 1 are treated as 1.
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Set the timeout for a single relay query performed by the `*_on_all_relays` methods.
+    /// Relays that do not answer in time result in an [`Error::Timeout`].
+    pub fn with_relay_timeout(mut self, relay_timeout: Duration) -> Self {
+        self.relay_timeout = relay_timeout;
+        self
+    }
+
+    /// Set the policy for retrying failed requests. Use [`RetryPolicy::none()`] to disable retries.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the policy for retrying failed block submissions and validator registrations.
+    ///
+    /// These requests are not idempotent, so they are not retried by default
+    /// ([`RetryPolicy::none()`]) and are not affected by [`Client::with_retry_policy()`].
+    pub fn with_submission_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.submission_retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the rate limit for requests to the relay with the given name, if present.
+    /// `None` makes the relay fall back to the client's default rate limit.
+    ///
+    /// See [`constants::DEFAULT_RATE_LIMITS`] for the limits applied to the default relays.
+    pub fn with_rate_limit(mut self, relay_name: &str, limit: Option<RateLimit>) -> Self {
+        if let Some(relay) = self.relays.get_mut(relay_name) {
+            relay.options.rate_limit = limit;
+        }
+        self
+    }
+
+    /// Set the chain spec of the network of the relays, used for signature verification
+    /// and epoch-based queries. Defaults to the spec of mainnet.
+    pub fn with_chain_spec(mut self, spec: ChainSpec) -> Self {
+        self.spec = spec;
+        self
+    }
+
+    /// Set the genesis fork version of the network of the relays, used to verify signatures.
+    /// Defaults to [`constants::MAINNET_GENESIS_FORK_VERSION`].
+    pub fn with_genesis_fork_version(mut self, genesis_fork_version: ForkVersion) -> Self {
+        self.spec.genesis_fork_version = genesis_fork_version;
+        self
+    }
+
+    /// Returns the chain spec of the network of the relays.
+    pub fn chain_spec(&self) -> &ChainSpec {
+        &self.spec
+    }
+
+    /// Verify the signatures of the validator registrations returned by
+    /// [`Client::get_validator_registration()`] and [`Client::get_validators_for_current_and_next_epoch()`].
+    /// An invalid registration results in an [`Error::InvalidRegistration`] for the former, and is
+    /// left out of the result of the latter. Disabled by default.
+    pub fn with_registration_verification(mut self, verify_registrations: bool) -> Self {
+        self.verify_registrations = verify_registrations;
+        self
+    }
+
+    /// Verify that the bids returned by [`Client::get_header()`] are signed by the relay,
+    /// with the pubkey of its URL. Bids with an invalid signature result in an
+    /// [`Error::InvalidBidSignature`]. Disabled by default, as the bid hash tree roots
+    /// have not been checked against bids signed by live relays yet.
+    pub fn with_bid_verification(mut self, verify_bids: bool) -> Self {
+        self.verify_bids = verify_bids;
+        self
+    }
+
+    /// Add a relay to the client, returning the previous relay with the same name, if any.
+    pub fn add_relay(&mut self, relay: Relay) -> Option<Relay> {
+        self.relays.insert(relay.name.clone(), relay)
+    }
+
+    /// Remove the relay with the given name from the client, returning it if it was present.
+    pub fn remove_relay(&mut self, relay_name: &str) -> Option<Relay> {
+        self.relays.remove(relay_name)
+    }
+
+    /// Returns the relay with the given name, if present.
+    pub fn relay(&self, relay_name: &str) -> Option<&Relay> {
+        self.relays.get(relay_name)
+    }
+
+    /// Iterate over all relays of the client.
+    pub fn relays(&self) -> impl Iterator<Item = &Relay> {
+        self.relays.values()
     }
 
     /// Check if the client contains a relay with the given name.
@@ -59,59 +261,86 @@ impl<'a> Client<'a> {
 
     /// Perform a relay query for validator registrations for the current and next epochs.
     ///
+    /// If enabled with [`Client::with_registration_verification()`], registrations with an
+    /// invalid signature are logged and left out of the result.
+    ///
     /// [Visit the docs](https://flashbots.github.io/relay-specs/#/Builder/getValidators) for more info.
     pub async fn get_validators_for_current_and_next_epoch(
         &self,
         relay_name: &str,
-    ) -> anyhow::Result<Vec<types::RegisteredValidator>> {
-        let relay_url = self.get_relay_url(relay_name)?;
-        let endpoint = format!("{}{}", relay_url, constants::GET_VALIDATORS_ENDPOINT);
-        let response = self.fetch(endpoint).await?;
+    ) -> Result<Vec<types::RegisteredValidator>> {
+        let relay = self.get_relay(relay_name)?;
+        let endpoint = relay.url.endpoint(constants::GET_VALIDATORS_ENDPOINT)?;
+        let validators: Vec<types::RegisteredValidator> = self.fetch(relay, endpoint, &()).await?;
 
-        serde_json::from_str::<Vec<types::RegisteredValidator>>(&response)
-            .map_err(|e| anyhow::anyhow!("Failed to parse JSON response: {}", e))
+        Ok(validators
+            .into_iter()
+            .filter(
+                |validator| match self.verify_registration(&validator.entry) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::warn!(
+                            "Dropping registration for slot {} from relay {}: {}",
+                            validator.slot,
+                            relay_name,
+                            e
+                        );
+                        false
+                    }
+                },
+            )
+            .collect())
     }
 
     /// Perform a relay query to check if a validator with the given pubkey
     /// is registered with the specified relay.
     ///
+    /// Returns [`Error::ValidatorNotRegistered`] if the relay has no registration for the pubkey.
+    ///
     /// [Visit the docs](https://flashbots.github.io/relay-specs/#/Data/getValidatorRegistration) for more info.
     pub async fn get_validator_registration(
         &self,
         relay_name: &str,
-        pubkey: &str,
-    ) -> anyhow::Result<types::ValidatorEntry> {
-        let relay_url = self.get_relay_url(relay_name)?;
-        let endpoint = format!(
-            "{}{}?pubkey={}",
-            relay_url,
-            constants::CHECK_VALIDATOR_REGISTRATION,
-            pubkey
-        );
-        let response = self.fetch(endpoint).await?;
+        pubkey: &BlsPublicKey,
+    ) -> Result<types::ValidatorEntry> {
+        let relay = self.get_relay(relay_name)?;
+        let endpoint = relay
+            .url
+            .endpoint(constants::CHECK_VALIDATOR_REGISTRATION)?;
+
+        let entry = self
+            .fetch(relay, endpoint, &[("pubkey", pubkey)])
+            .await
+            .map_err(|e| map_not_registered(e, relay_name, pubkey))?;
+
+        self.verify_registration(&entry)?;
+        Ok(entry)
+    }
 
-        serde_json::from_str::<types::ValidatorEntry>(&response)
-            .map_err(|e| anyhow::anyhow!("Failed to parse JSON response: {}", e))
+    /// Verify the signature of a registration returned by a relay, if enabled.
+    fn verify_registration(
+        &self,
+        registration: &consensus::SignedValidatorRegistration,
+    ) -> Result<()> {
+        if self.verify_registrations {
+            registration.verify(self.spec.genesis_fork_version)?;
+        }
+        Ok(())
     }
 
     /// Perform a relay query to get the payloads delivered by the relay to the proposer.
     /// Query options act as filters.
+    ///
+    /// Returns [`Error::InvalidQuery`] without sending a request if the options are invalid.
     pub async fn get_payload_delivered_bidtraces(
         &self,
         relay_name: &str,
         opts: &types::PayloadDeliveredQueryOptions,
-    ) -> anyhow::Result<Vec<types::PayloadBidtrace>> {
-        let relay_url = self.get_relay_url(relay_name)?;
-        let endpoint = format!(
-            "{}{}{}",
-            relay_url,
-            constants::GET_DELIVERED_PAYLOADS,
-            opts.to_string()
-        );
-        let response = self.fetch(endpoint).await?;
-
-        serde_json::from_str::<Vec<types::PayloadBidtrace>>(&response)
-            .map_err(|e| anyhow::anyhow!("Failed to parse JSON response: {}", e))
+    ) -> Result<Vec<types::PayloadBidtrace>> {
+        let relay = self.get_relay(relay_name)?;
+        opts.validate()?;
+        let endpoint = relay.url.endpoint(constants::GET_DELIVERED_PAYLOADS)?;
+        self.fetch(relay, endpoint, opts).await
     }
 
     /// Perform queries on all relays to get the payloads delivered by each relay to proposers.
@@ -119,45 +348,36 @@ impl<'a> Client<'a> {
     pub async fn get_payloads_delivered_bidtraces_on_all_relays(
         &self,
         opts: &types::PayloadDeliveredQueryOptions,
-    ) -> anyhow::Result<HashMap<&'a str, Vec<types::PayloadBidtrace>>> {
-        let mut payloads_delivered = HashMap::new();
-        for relay_name in self.relays.keys() {
-            match self.get_payload_delivered_bidtraces(relay_name, opts).await {
-                Ok(relay_res) => {
-                    payloads_delivered.insert(*relay_name, relay_res);
-                }
-                Err(e) => {
-                    tracing::warn!(
-                        "Failed to get payloads delivered for relay {}: {}",
-                        relay_name,
-                        e
-                    );
-                    continue;
-                }
-            }
+    ) -> Result<HashMap<String, Vec<types::PayloadBidtrace>>> {
+        opts.validate()?;
+        let responses = self
+            .query_all_relays(|relay_name| self.get_payload_delivered_bidtraces(relay_name, opts))
+            .await;
+
+        for (relay_name, e) in responses.failures() {
+            tracing::warn!(
+                "Failed to get payloads delivered for relay {}: {}",
+                relay_name,
+                e
+            );
         }
 
-        Ok(payloads_delivered)
+        Ok(responses.into_successes())
     }
 
     /// Perform a relay query to get the builder bid submissions.
     /// Query options act as filters.
+    ///
+    /// Returns [`Error::InvalidQuery`] without sending a request if the options are invalid.
     pub async fn get_builder_blocks_received(
         &self,
         relay_name: &str,
         opts: &types::BuilderBidsReceivedOptions,
-    ) -> anyhow::Result<Vec<types::BuilderBlockBidtrace>> {
-        let relay_url = self.get_relay_url(relay_name)?;
-        let endpoint = format!(
-            "{}{}{}",
-            relay_url,
-            constants::GET_BUILDER_BLOCKS_RECEIVED,
-            opts.to_string()
-        );
-        let response = self.fetch(endpoint).await?;
-
-        serde_json::from_str::<Vec<types::BuilderBlockBidtrace>>(&response)
-            .map_err(|e| anyhow::anyhow!("Failed to parse JSON response: {}", e))
+    ) -> Result<Vec<types::BuilderBlockBidtrace>> {
+        let relay = self.get_relay(relay_name)?;
+        opts.validate()?;
+        let endpoint = relay.url.endpoint(constants::GET_BUILDER_BLOCKS_RECEIVED)?;
+        self.fetch(relay, endpoint, opts).await
     }
 
     /// Perform queries on all relays to get the builder bid submissions.
@@ -165,25 +385,21 @@ impl<'a> Client<'a> {
     pub async fn get_builder_blocks_received_on_all_relays(
         &self,
         opts: &types::BuilderBidsReceivedOptions,
-    ) -> anyhow::Result<HashMap<&'a str, Vec<types::BuilderBlockBidtrace>>> {
-        let mut builder_blocks_received = HashMap::new();
-        for relay_name in self.relays.keys() {
-            match self.get_builder_blocks_received(relay_name, opts).await {
-                Ok(relay_res) => {
-                    builder_blocks_received.insert(*relay_name, relay_res);
-                }
-                Err(e) => {
-                    tracing::warn!(
-                        "Failed to get builder blocks received for relay {}: {}",
-                        relay_name,
-                        e
-                    );
-                    continue;
-                }
-            }
+    ) -> Result<HashMap<String, Vec<types::BuilderBlockBidtrace>>> {
+        opts.validate()?;
+        let responses = self
+            .query_all_relays(|relay_name| self.get_builder_blocks_received(relay_name, opts))
+            .await;
+
+        for (relay_name, e) in responses.failures() {
+            tracing::warn!(
+                "Failed to get builder blocks received for relay {}: {}",
+                relay_name,
+                e
+            );
         }
 
-        Ok(builder_blocks_received)
+        Ok(responses.into_successes())
     }
 
     /// Perform a relay query to check if a validator with the given pubkey
@@ -192,55 +408,299 @@ impl<'a> Client<'a> {
     /// given relay, it will not be included in the hashmap.
     pub async fn get_validator_registration_on_all_relays(
         &self,
-        pubkey: &str,
-    ) -> anyhow::Result<HashMap<&'a str, types::ValidatorEntry>> {
-        let mut validator_registrations = HashMap::new();
-        for relay_name in self.relays.keys() {
-            match self.get_validator_registration(relay_name, pubkey).await {
-                Ok(relay_res) => {
-                    validator_registrations.insert(*relay_name, relay_res);
-                }
-                Err(e) => {
-                    tracing::warn!(
-                        "Failed to get validator registration for pubkey {} on relay {}: {}",
-                        pubkey,
-                        relay_name,
-                        e
-                    );
-                    continue;
-                }
-            }
+        pubkey: &BlsPublicKey,
+    ) -> Result<HashMap<String, types::ValidatorEntry>> {
+        let responses = self
+            .query_all_relays(|relay_name| self.get_validator_registration(relay_name, pubkey))
+            .await;
+
+        for (relay_name, e) in responses.failures() {
+            tracing::warn!(
+                "Failed to get validator registration for pubkey {} on relay {}: {}",
+                pubkey,
+                relay_name,
+                e
+            );
         }
 
-        Ok(validator_registrations)
+        Ok(responses.into_successes())
+    }
+
+    /// Perform a builder API query to get the best bid of the relay for the given slot,
+    /// parent block hash and proposer pubkey.
+    ///
+    /// Returns `None` if the relay has no bid (HTTP 204 No Content). If enabled with
+    /// [`Client::with_bid_verification()`], fails with [`Error::InvalidBidSignature`]
+    /// if the bid is not signed by the relay.
+    ///
+    /// [Visit the docs](https://ethereum.github.io/builder-specs/#/Builder/getHeader) for more info.
+    pub async fn get_header(
+        &self,
+        relay_name: &str,
+        slot: u64,
+        parent_hash: &Hash32,
+        pubkey: &BlsPublicKey,
+    ) -> Result<Option<consensus::SignedBuilderBid>> {
+        let relay = self.get_relay(relay_name)?;
+        let endpoint = relay.url.endpoint(&format!(
+            "{}/{}/{}/{}",
+            constants::GET_HEADER,
+            slot,
+            parent_hash,
+            pubkey
+        ))?;
+
+        let request = self
+            .inner
+            .get(endpoint)
+            .header("accept", "application/json");
+
+        let response = self.send(relay, request).await?;
+        if response.status() == reqwest::StatusCode::NO_CONTENT {
+            return Ok(None);
+        }
+
+        let bid: consensus::SignedBuilderBid = parse_json(response).await?;
+        if self.verify_bids {
+            bid.verify(relay.pubkey(), self.spec.genesis_fork_version)?;
+        }
+        Ok(Some(bid))
+    }
+
+    /// Perform builder API queries on all relays to get their best bid for the given slot,
+    /// parent block hash and proposer pubkey. Returns a hashmap of relay names to bids.
+    /// Relays without a bid are not included in the hashmap.
+    pub async fn get_header_on_all_relays(
+        &self,
+        slot: u64,
+        parent_hash: &Hash32,
+        pubkey: &BlsPublicKey,
+    ) -> Result<HashMap<String, consensus::SignedBuilderBid>> {
+        let responses = self
+            .query_all_relays(|relay_name| self.get_header(relay_name, slot, parent_hash, pubkey))
+            .await;
+
+        for (relay_name, e) in responses.failures() {
+            tracing::warn!(
+                "Failed to get header for slot {} on relay {}: {}",
+                slot,
+                relay_name,
+                e
+            );
+        }
+
+        Ok(responses
+            .into_successes()
+            .into_iter()
+            .filter_map(|(relay_name, bid)| bid.map(|bid| (relay_name, bid)))
+            .collect())
+    }
+
+    /// Submit a signed blinded block to the relay that provided its header, and get back
+    /// the execution payload (and blobs bundle, from the Deneb fork onwards) of the block.
+    ///
+    /// The fork of the block is sent in the `Eth-Consensus-Version` header. Fails with
+    /// [`Error::PayloadMismatch`] if the returned payload is not of the same fork or does
+    /// not have the same block hash as the submitted block.
+    ///
+    /// [Visit the docs](https://ethereum.github.io/builder-specs/#/Builder/submitBlindedBlock) for more info.
+    pub async fn submit_blinded_block(
+        &self,
+        relay_name: &str,
+        block: &consensus::SignedBlindedBeaconBlock,
+    ) -> Result<consensus::GetPayloadResponse> {
+        let relay = self.get_relay(relay_name)?;
+        let endpoint = relay.url.endpoint(constants::SUBMIT_BLINDED_BLOCK)?;
+        let body = serde_json::to_vec(block).map_err(Error::Serialize)?;
+
+        let request = self
+            .inner
+            .post(endpoint)
+            .header("content-type", "application/json")
+            .header("accept", "application/json")
+            .header(
+                constants::CONSENSUS_VERSION_HEADER,
+                block.fork().to_string(),
+            )
+            .body(body);
+
+        let payload: consensus::GetPayloadResponse =
+            parse_json(self.send(relay, request).await?).await?;
+
+        let mismatch = |reason: String| Error::PayloadMismatch {
+            relay: relay_name.to_string(),
+            reason,
+        };
+        if payload.fork() != block.fork() {
+            return Err(mismatch(format!(
+                "expected fork {}, got {}",
+                block.fork(),
+                payload.fork()
+            )));
+        }
+        if payload.block_hash() != block.block_hash() {
+            return Err(mismatch(format!(
+                "expected block hash {}, got {}",
+                block.block_hash(),
+                payload.block_hash()
+            )));
+        }
+
+        Ok(payload)
+    }
+
+    /// Submit a block built by a builder to the relay, to compete in the auction of its slot.
+    ///
+    /// The body is encoded as JSON or SSZ and optionally gzipped, as set in the `options`.
+    /// Failed submissions are only retried if enabled with [`Client::with_submission_retry_policy()`].
+    /// Fails with [`Error::Serialize`] before sending if the blobs bundle is malformed,
+    /// see [`consensus::BlobsBundle::validate()`].
+    ///
+    /// [Visit the docs](https://flashbots.github.io/relay-specs/#/Builder/submitBlock) for more info.
+    pub async fn submit_block(
+        &self,
+        relay_name: &str,
+        request: &types::SubmitBlockRequest,
+        options: &types::SubmitBlockOptions,
+    ) -> Result<()> {
+        let relay = self.get_relay(relay_name)?;
+        let endpoint = relay.url.endpoint(constants::SUBMIT_BLOCK)?;
+        if let Some(blobs_bundle) = request.blobs_bundle() {
+            blobs_bundle.validate()?;
+        }
+
+        let (content_type, mut body) = match options.encoding {
+            types::SubmissionEncoding::Json => (
+                "application/json",
+                serde_json::to_vec(request).map_err(Error::Serialize)?,
+            ),
+            types::SubmissionEncoding::Ssz => ("application/octet-stream", request.as_ssz_bytes()),
+        };
+        if options.gzip {
+            body = gzip(&body);
+        }
+
+        let mut builder = self
+            .inner
+            .post(endpoint)
+            .header("content-type", content_type)
+            .header(
+                constants::CONSENSUS_VERSION_HEADER,
+                request.fork().to_string(),
+            );
+        if options.cancellations {
+            builder = builder.query(&[("cancellations", "1")]);
+        }
+        if options.gzip {
+            builder = builder.header("content-encoding", "gzip");
+        }
+
+        self.send_with_policy(relay, builder.body(body), &self.submission_retry_policy)
+            .await?;
+        Ok(())
+    }
+
+    /// Register validators with the relay, to receive bids built with their preferences.
+    ///
+    /// Failed registrations are only retried if enabled with [`Client::with_submission_retry_policy()`].
+    ///
+    /// [Visit the docs](https://ethereum.github.io/builder-specs/#/Builder/registerValidator) for more info.
+    pub async fn register_validators(
+        &self,
+        relay_name: &str,
+        registrations: &[consensus::SignedValidatorRegistration],
+    ) -> Result<()> {
+        let relay = self.get_relay(relay_name)?;
+        let endpoint = relay.url.endpoint(constants::REGISTER_VALIDATORS)?;
+        let body = serde_json::to_vec(registrations).map_err(Error::Serialize)?;
+
+        let request = self
+            .inner
+            .post(endpoint)
+            .header("content-type", "application/json")
+            .body(body);
+
+        self.send_with_policy(relay, request, &self.submission_retry_policy)
+            .await?;
+        Ok(())
+    }
+
+    /// Register validators with all relays concurrently, returning the outcome of each relay.
+    pub async fn register_validators_on_all_relays(
+        &self,
+        registrations: &[consensus::SignedValidatorRegistration],
+    ) -> MultiRelayResponse<()> {
+        self.query_all_relays(|relay_name| self.register_validators(relay_name, registrations))
+            .await
+    }
+
+    /// Perform a builder API query to check that the relay is up and ready to serve requests.
+    ///
+    /// Returns an error if the relay is down or responds with a non-success status.
+    ///
+    /// [Visit the docs](https://ethereum.github.io/builder-specs/#/Builder/status) for more info.
+    pub async fn status(&self, relay_name: &str) -> Result<()> {
+        let relay = self.get_relay(relay_name)?;
+        let endpoint = relay.url.endpoint(constants::GET_STATUS)?;
+
+        self.send(relay, self.inner.get(endpoint)).await?;
+        Ok(())
+    }
+
+    /// Check the status of all relays concurrently. A relay is up if its outcome is `Ok`,
+    /// and the latency of each check is reported along with its outcome.
+    ///
+    /// Transient failures are retried according to the client's [`RetryPolicy`],
+    /// so a relay is only reported as down if it is still failing after all attempts.
+    pub async fn health_check_all(&self) -> MultiRelayResponse<()> {
+        self.query_all_relays(|relay_name| self.status(relay_name))
+            .await
     }
 
     /// Performs the following steps:
-    /// 1. Get validator registrations for the current and next epochs for all relays
+    /// 1. Get validator registrations for the current and next epochs for all relays,
+    ///    skipping the relays that fail
     /// 2. Build a map of slot number to relay names that have a validator registered for that slot
+    ///
+    /// Every slot of the current and next epochs, according to the client's [`ChainSpec`],
+    /// is in the map, with no relay names if no validator is registered for it. The current
+    /// epoch is the epoch of the earliest slot returned by the relays, or the epoch of the
+    /// wall clock when the query started if they returned none.
     pub async fn get_validator_registration_for_all_slots_on_all_relays(
         &self,
-    ) -> anyhow::Result<HashMap<u64, Vec<&'a str>>> {
+    ) -> Result<HashMap<u64, Vec<String>>> {
+        let clock_epoch = self.spec.slot_clock().current_epoch();
         let mut validator_registrations = HashMap::new();
+        let responses = self
+            .query_all_relays(|relay_name| {
+                self.get_validators_for_current_and_next_epoch(relay_name)
+            })
+            .await;
 
-        for relay_name in self.relays.keys() {
-            let relay_res = self
-                .get_validators_for_current_and_next_epoch(relay_name)
-                .await?;
+        for (relay_name, e) in responses.failures() {
+            tracing::warn!("Failed to get validators for relay {}: {}", relay_name, e);
+        }
 
-            for validator in relay_res {
+        for (relay_name, validators) in responses.into_successes() {
+            for validator in validators {
                 let relay_names = validator_registrations
                     .entry(validator.slot)
                     .or_insert_with(Vec::new);
 
-                relay_names.push(*relay_name);
+                relay_names.push(relay_name.clone());
             }
         }
 
-        // Fill all slots with no registrations with an empty vector.
-        // The total number of slots for current + next epoch is 32 + 32 = 64.
-        if let Some(initial_slot) = validator_registrations.keys().min() {
-            for slot in *initial_slot..(*initial_slot + 63) {
+        // Fill all slots with no registrations with an empty vector,
+        // for the slots of the current and next epochs.
+        let current_epoch = validator_registrations
+            .keys()
+            .min()
+            .map(|slot| self.spec.epoch_of_slot(*slot))
+            .or(clock_epoch);
+        if let Some(current_epoch) = current_epoch {
+            let first_slot = self.spec.start_slot_of_epoch(current_epoch);
+            for slot in first_slot..first_slot + 2 * self.spec.slots_per_epoch {
                 validator_registrations.entry(slot).or_insert_with(Vec::new);
             }
         }
@@ -249,7 +709,7 @@ impl<'a> Client<'a> {
     }
 
     /// Returns a list of slot numbers for which no relays are registered for the current and next epochs.
-    pub async fn get_vanilla_slots_for_current_and_next_epoch(&self) -> anyhow::Result<Vec<u64>> {
+    pub async fn get_vanilla_slots_for_current_and_next_epoch(&self) -> Result<Vec<u64>> {
         let all = self
             .get_validator_registration_for_all_slots_on_all_relays()
             .await?;
@@ -261,35 +721,278 @@ impl<'a> Client<'a> {
             .collect())
     }
 
-    /// Helper function to perform an HTTP get request with standard headers.
-    async fn fetch(&self, endpoint: String) -> anyhow::Result<String> {
-        let response = self
+    /// Run a query on all relays concurrently, returning the outcome of each relay.
+    ///
+    /// At most `max_concurrency` queries are in flight at the same time, and each query
+    /// fails with [`Error::Timeout`] after `relay_timeout`. Returns once every relay
+    /// has answered or timed out.
+    ///
+    /// ```no_run
+    /// # async fn example() {
+    /// let client = mevboost_relay_api::Client::default();
+    /// let opts = Default::default();
+    /// let responses = client
+    ///     .query_all_relays(|relay| client.get_payload_delivered_bidtraces(relay, &opts))
+    ///     .await;
+    ///
+    /// println!("{}", responses.summary());
+    /// for (relay, err) in responses.failures() {
+    ///     println!("{relay} failed: {err}");
+    /// }
+    /// # }
+    /// ```
+    pub async fn query_all_relays<'s, T, F, Fut>(&'s self, query: F) -> MultiRelayResponse<T>
+    where
+        F: Fn(&'s str) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        self.query_relays(self.relays.keys().map(String::as_str), query)
+            .await
+    }
+
+    /// Helper function to run a query on the given relays concurrently,
+    /// returning the outcome of each relay.
+    pub(crate) async fn query_relays<'s, T, F, Fut>(
+        &self,
+        relay_names: impl IntoIterator<Item = &'s str>,
+        query: F,
+    ) -> MultiRelayResponse<T>
+    where
+        F: Fn(&'s str) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let timeout = self.relay_timeout;
+        let query = &query;
+
+        // The queries are collected before being streamed, so that the returned future
+        // does not hold a closure over the relay names and stays `Send`.
+        let queries: Vec<_> = relay_names
+            .into_iter()
+            .map(|relay_name| async move {
+                let start = Instant::now();
+                let result = tokio::time::timeout(timeout, query(relay_name))
+                    .await
+                    .unwrap_or(Err(Error::Timeout(timeout)));
+                let latency = start.elapsed();
+
+                (relay_name.to_string(), RelayResponse { result, latency })
+            })
+            .collect();
+
+        stream::iter(queries)
+            .buffer_unordered(self.max_concurrency)
+            .collect()
+            .await
+    }
+
+    /// Helper function to perform an HTTP get request with standard headers
+    /// and the given URL-encoded query, and deserialize the JSON response body.
+    async fn fetch<T, Q>(&self, relay: &Relay, endpoint: reqwest::Url, query: &Q) -> Result<T>
+    where
+        T: DeserializeOwned,
+        Q: Serialize + ?Sized,
+    {
+        let request = self
             .inner
             .request(reqwest::Method::GET, endpoint)
+            .query(query)
             .header("content-type", "application/json")
-            .header("accept", "application/json")
-            .send()
-            .await?
-            .text()
-            .await?;
+            .header("accept", "application/json");
+
+        parse_json(self.send(relay, request).await?).await
+    }
+
+    /// Helper function to send a request to a relay, retrying transient failures according
+    /// to the client's [`RetryPolicy`].
+    async fn send(
+        &self,
+        relay: &Relay,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response> {
+        self.send_with_policy(relay, request, &self.retry_policy)
+            .await
+    }
+
+    /// Helper function to send a request to a relay, retrying transient failures according
+    /// to the given [`RetryPolicy`]. Every attempt waits for the relay's rate limit.
+    ///
+    /// Non-success responses are turned into [`Error::Relay`] if the body contains
+    /// an error object as defined in the relay specs, or [`Error::Status`] otherwise.
+    async fn send_with_policy(
+        &self,
+        relay: &Relay,
+        mut request: reqwest::RequestBuilder,
+        policy: &RetryPolicy,
+    ) -> Result<reqwest::Response> {
+        let mut attempt = 1;
+
+        if let Some(timeout) = relay.options.timeout {
+            request = request.timeout(timeout);
+        }
 
-        Ok(response)
+        loop {
+            // Requests with streaming bodies cannot be cloned, and thus cannot be retried.
+            let next_request = if attempt < policy.max_attempts {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            self.rate_limiter.acquire(relay).await;
+
+            let span = tracing::debug_span!("relay_request", relay = %relay.name, attempt);
+            let (error, retry_after) = match request.send().instrument(span).await {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) => {
+                    let retry_after = retry::parse_retry_after(response.headers());
+                    (error_from_response(response).await, retry_after)
+                }
+                Err(e) => (Error::Transport(e), None),
+            };
+
+            request = match next_request {
+                Some(next_request) if policy.is_retryable(&error) => next_request,
+                _ => return Err(error),
+            };
+
+            let delay = match retry_after {
+                Some(delay) if delay > policy.max_retry_after => return Err(error),
+                Some(delay) => delay,
+                None => policy.backoff(attempt),
+            };
+
+            tracing::debug!(attempt, ?delay, %error, "Relay request failed, retrying");
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
     }
 
-    /// Helper function to get the URL for a given relay name.
-    fn get_relay_url(&self, relay_name: &str) -> anyhow::Result<&str> {
+    /// Helper function to get the relay with the given name.
+    fn get_relay(&self, relay_name: &str) -> Result<&Relay> {
         self.relays
             .get(relay_name)
-            .map(|x| x.to_owned())
-            .ok_or(anyhow::anyhow!(
-                "Relay `{}` not found in list of relays",
-                relay_name
-            ))
+            .ok_or_else(|| Error::UnknownRelay(relay_name.to_string()))
+    }
+}
+
+/// Helper function to map the error of a relay without a registration for the
+/// given pubkey to [`Error::ValidatorNotRegistered`].
+fn map_not_registered(e: Error, relay_name: &str, pubkey: &BlsPublicKey) -> Error {
+    // Relays respond with either 404 or 400 and a "no registration found" message.
+    let no_registration_found = match &e {
+        Error::Relay { error, .. } => error.message.contains("no registration found"),
+        _ => false,
+    };
+
+    if no_registration_found || e.status() == Some(reqwest::StatusCode::NOT_FOUND) {
+        Error::ValidatorNotRegistered {
+            relay: relay_name.to_string(),
+            pubkey: pubkey.to_string(),
+        }
+    } else {
+        e
+    }
+}
+
+/// Compress a request body with gzip.
+fn gzip(body: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(body)
+        .expect("Writing to a Vec cannot fail");
+    encoder.finish().expect("Writing to a Vec cannot fail")
+}
+
+/// Helper function to deserialize the JSON body of a response.
+async fn parse_json<T: DeserializeOwned>(response: reqwest::Response) -> Result<T> {
+    let body = response.text().await?;
+    serde_json::from_str::<T>(&body).map_err(|source| Error::Deserialize { source, body })
+}
+
+/// Helper function to turn a non-success response into an [`Error`].
+async fn error_from_response(response: reqwest::Response) -> Error {
+    let status = response.status();
+    let body = match response.text().await {
+        Ok(body) => body,
+        Err(e) => return Error::Transport(e),
+    };
+
+    match serde_json::from_str::<types::RelayErrorResponse>(&body) {
+        Ok(error) => Error::Relay { status, error },
+        Err(_) => Error::Status { status, body },
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+    use wiremock::{
+        matchers::{body_json, header, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::{
+        consensus::{
+            BeaconBlock, BlindedBeaconBlockBodyDeneb, BlobsBundle, ExecutionPayloadAndBlobsBundle,
+            ExecutionPayloadDeneb, ExecutionPayloadHeaderDeneb, Fork, GetPayloadResponse, Signed,
+            SignedBlindedBeaconBlock, SignedBuilderBid, SignedValidatorRegistration,
+            ValidatorRegistration,
+        },
+        constants,
+        primitives::{Address, BlsPublicKey, BlsSignature, Bytes, Hash32, Wei},
+        signing::tests::{secret_key, signed_bid, signed_registration},
+        ssz::{Encode, BYTES_PER_BLOB},
+        types::{
+            BidTrace, SubmissionEncoding, SubmitBlockOptions, SubmitBlockRequest,
+            SubmitBlockRequestDeneb,
+        },
+        Error, RateLimit, Relay, RetryPolicy,
+    };
+
+    /// Pubkey of the relays pointing to mock servers.
+    pub(crate) const MOCK_RELAY_PUBKEY: &str = "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae";
+
+    /// Helper function to create a relay pointing to a mock server.
+    pub(crate) fn mock_relay(name: &str, server: &MockServer) -> Relay {
+        mock_relay_with_pubkey(name, server, MOCK_RELAY_PUBKEY)
+    }
+
+    /// Helper function to create a relay with the given pubkey pointing to a mock server.
+    pub(crate) fn mock_relay_with_pubkey(name: &str, server: &MockServer, pubkey: &str) -> Relay {
+        let url = server.uri().replace("://", &format!("://{pubkey}@"));
+        Relay::new(name, &url).unwrap()
+    }
+
+    /// Helper function to create a signed Deneb blinded block with the given block hash,
+    /// and the payload a relay returns for it.
+    fn deneb_blinded_block(
+        block_hash: Hash32,
+    ) -> (SignedBlindedBeaconBlock, ExecutionPayloadAndBlobsBundle) {
+        let block = SignedBlindedBeaconBlock::Deneb(Signed {
+            message: BeaconBlock {
+                slot: 1,
+                body: BlindedBeaconBlockBodyDeneb {
+                    execution_payload_header: ExecutionPayloadHeaderDeneb {
+                        block_hash,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            signature: BlsSignature::repeat_byte(0xcd),
+        });
+        let payload = ExecutionPayloadAndBlobsBundle {
+            execution_payload: ExecutionPayloadDeneb {
+                block_hash,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        (block, payload)
+    }
 
     #[tokio::test]
     async fn test_get_validator_registrations_for_current_and_next_epoch() -> anyhow::Result<()> {
@@ -305,9 +1008,9 @@ mod tests {
     #[tokio::test]
     async fn test_get_validator_registration() -> anyhow::Result<()> {
         let client = super::Client::default();
-        let pubkey = "0xacb2e8af472337d76290b8da9345d4edf6a5f7ce573a319340ce53112551f465878d996ad6745b80b64db1104e20c5d3";
+        let pubkey: BlsPublicKey = "0xacb2e8af472337d76290b8da9345d4edf6a5f7ce573a319340ce53112551f465878d996ad6745b80b64db1104e20c5d3".parse()?;
         let response = client
-            .get_validator_registration("flashbots", pubkey)
+            .get_validator_registration("flashbots", &pubkey)
             .await?;
 
         assert_eq!(response.message.pubkey, pubkey);
@@ -317,9 +1020,9 @@ mod tests {
     #[tokio::test]
     async fn test_get_validator_registration_on_all_relays() -> anyhow::Result<()> {
         let client = super::Client::default();
-        let pubkey = "0xacb2e8af472337d76290b8da9345d4edf6a5f7ce573a319340ce53112551f465878d996ad6745b80b64db1104e20c5d3";
+        let pubkey: BlsPublicKey = "0xacb2e8af472337d76290b8da9345d4edf6a5f7ce573a319340ce53112551f465878d996ad6745b80b64db1104e20c5d3".parse()?;
         let response = client
-            .get_validator_registration_on_all_relays(pubkey)
+            .get_validator_registration_on_all_relays(&pubkey)
             .await?;
 
         assert!(!response.is_empty());
@@ -379,4 +1082,811 @@ mod tests {
         assert!(!response.is_empty());
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_relay_error_response() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(constants::GET_DELIVERED_PAYLOADS))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .set_body_string(r#"{"code":429,"message":"rate limit exceeded"}"#),
+            )
+            .mount(&server)
+            .await;
+
+        let client = super::Client::with_relays([mock_relay("mock", &server)]);
+        let err = client
+            .get_payload_delivered_bidtraces("mock", &Default::default())
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.status(), Some(reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(
+            matches!(err, Error::Relay { error, .. } if error.message == "rate limit exceeded")
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_non_json_error_response() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(502).set_body_string("Bad Gateway"))
+            .mount(&server)
+            .await;
+
+        let client = super::Client::with_relays([mock_relay("mock", &server)]);
+        let err = client
+            .get_validators_for_current_and_next_epoch("mock")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, Error::Status { body, .. } if body == "Bad Gateway"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_validator_not_registered() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(constants::CHECK_VALIDATOR_REGISTRATION))
+            .and(query_param("pubkey", MOCK_RELAY_PUBKEY))
+            .respond_with(
+                ResponseTemplate::new(400).set_body_string(
+                    r#"{"code":400,"message":"no registration found for validator"}"#,
+                ),
+            )
+            .mount(&server)
+            .await;
+
+        let client = super::Client::with_relays([mock_relay("mock", &server)]);
+        let err = client
+            .get_validator_registration("mock", &MOCK_RELAY_PUBKEY.parse()?)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, Error::ValidatorNotRegistered { relay, .. } if relay == "mock"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_options_encoding_and_validation() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(constants::GET_DELIVERED_PAYLOADS))
+            .and(query_param("cursor", "100"))
+            .and(query_param("order_by", "-value"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = super::Client::with_relays([mock_relay("mock", &server)]);
+        let opts = super::types::PayloadDeliveredQueryOptions {
+            cursor: Some(100),
+            order_by: Some(super::types::OrderBy::ValueDesc),
+            ..Default::default()
+        };
+        client
+            .get_payload_delivered_bidtraces("mock", &opts)
+            .await?;
+
+        // Invalid options fail before any request is sent.
+        let opts = super::types::PayloadDeliveredQueryOptions {
+            slot: Some(1),
+            ..opts
+        };
+        let err = client
+            .get_payload_delivered_bidtraces("mock", &opts)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidQuery(_)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_header() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        let parent_hash = Hash32::repeat_byte(0x11);
+        let pubkey: BlsPublicKey = MOCK_RELAY_PUBKEY.parse()?;
+        let relay_key = secret_key(1);
+        let bid = signed_bid(&relay_key, false);
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "{}/1/{}/{}",
+                constants::GET_HEADER,
+                parent_hash,
+                pubkey
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&bid))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+
+        let relay_pubkey = bid.pubkey().to_string();
+        let client =
+            super::Client::with_relays([mock_relay_with_pubkey("mock", &server, &relay_pubkey)])
+                .with_bid_verification(true);
+        let bid = client.get_header("mock", 1, &parent_hash, &pubkey).await?;
+        assert_eq!(bid.map(|bid| bid.fork()), Some(Fork::Deneb));
+
+        let bid = client.get_header("mock", 2, &parent_hash, &pubkey).await?;
+        assert!(bid.is_none());
+
+        let bids = client
+            .get_header_on_all_relays(2, &parent_hash, &pubkey)
+            .await?;
+        assert!(bids.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_header_bid_verification() -> anyhow::Result<()> {
+        let parent_hash = Hash32::repeat_byte(0x11);
+        let pubkey: BlsPublicKey = MOCK_RELAY_PUBKEY.parse()?;
+
+        // The relay serves a valid bid signed with another key than the one of its URL.
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(signed_bid(&secret_key(1), true)),
+            )
+            .mount(&server)
+            .await;
+
+        // Bids are not verified by default.
+        let client = super::Client::with_relays([mock_relay("mock", &server)]);
+        let bid = client.get_header("mock", 1, &parent_hash, &pubkey).await?;
+        assert_eq!(bid.map(|bid| bid.fork()), Some(Fork::Electra));
+
+        let client = client.with_bid_verification(true);
+        let err = client
+            .get_header("mock", 1, &parent_hash, &pubkey)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidBidSignature { pubkey, .. } if pubkey == MOCK_RELAY_PUBKEY)
+        );
+
+        let bids = client
+            .get_header_on_all_relays(1, &parent_hash, &pubkey)
+            .await?;
+        assert!(bids.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_header_bid_over_list_limit() -> anyhow::Result<()> {
+        let parent_hash = Hash32::repeat_byte(0x11);
+        let pubkey: BlsPublicKey = MOCK_RELAY_PUBKEY.parse()?;
+
+        // The extra data of a header is limited to 32 bytes, so the bid has no hash tree root.
+        let mut bid = signed_bid(&secret_key(1), false);
+        if let SignedBuilderBid::Deneb(deneb) = &mut bid {
+            deneb.message.header.extra_data = vec![0x42; 33].into();
+        }
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&bid))
+            .mount(&server)
+            .await;
+
+        let relay_pubkey = bid.pubkey().to_string();
+        let client =
+            super::Client::with_relays([mock_relay_with_pubkey("mock", &server, &relay_pubkey)])
+                .with_bid_verification(true);
+        let err = client
+            .get_header("mock", 1, &parent_hash, &pubkey)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, Error::InvalidBidSignature { reason, .. } if reason.contains("maximum length of 32")),
+            "{err}"
+        );
+
+        let bids = client
+            .get_header_on_all_relays(1, &parent_hash, &pubkey)
+            .await?;
+        assert!(bids.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_health_check_all() -> anyhow::Result<()> {
+        let up = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(constants::GET_STATUS))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&up)
+            .await;
+
+        let down = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(constants::GET_STATUS))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&down)
+            .await;
+
+        let client = super::Client::with_relays([mock_relay("up", &up), mock_relay("down", &down)])
+            .with_retry_policy(RetryPolicy::none());
+        let health = client.health_check_all().await;
+
+        assert!(health.get("up").is_some_and(|res| res.is_ok()));
+        let err = health.get("down").unwrap().result.as_ref().unwrap_err();
+        assert_eq!(err.status(), Some(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+        assert_eq!(health.summary().failed, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_register_validators() -> anyhow::Result<()> {
+        let registration = SignedValidatorRegistration {
+            message: ValidatorRegistration {
+                fee_recipient: Address::repeat_byte(0xab),
+                gas_limit: 30_000_000,
+                timestamp: 1_700_000_000,
+                pubkey: MOCK_RELAY_PUBKEY.parse()?,
+            },
+            signature: BlsSignature::repeat_byte(0xcd),
+        };
+        let expected = serde_json::json!([{
+            "message": {
+                "fee_recipient": format!("0x{}", "ab".repeat(20)),
+                "gas_limit": "30000000",
+                "timestamp": "1700000000",
+                "pubkey": MOCK_RELAY_PUBKEY,
+            },
+            "signature": format!("0x{}", "cd".repeat(96)),
+        }]);
+
+        let accepting = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(constants::REGISTER_VALIDATORS))
+            .and(body_json(&expected))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&accepting)
+            .await;
+
+        let rejecting = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(400)
+                    .set_body_string(r#"{"code":400,"message":"invalid signature"}"#),
+            )
+            .mount(&rejecting)
+            .await;
+
+        let client = super::Client::with_relays([
+            mock_relay("accepting", &accepting),
+            mock_relay("rejecting", &rejecting),
+        ]);
+        let responses = client
+            .register_validators_on_all_relays(&[registration])
+            .await;
+
+        assert!(responses.get("accepting").is_some_and(|res| res.is_ok()));
+        let err = responses
+            .get("rejecting")
+            .unwrap()
+            .result
+            .as_ref()
+            .unwrap_err();
+        assert!(matches!(err, Error::Relay { error, .. } if error.message == "invalid signature"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_registrations_for_all_slots_of_current_and_next_epochs() -> anyhow::Result<()> {
+        // Genesis is in the future, so the slots must be derived from the relay responses.
+        let spec = crate::ChainSpec::default().with_genesis_time(32_503_680_000);
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(constants::GET_VALIDATORS_ENDPOINT))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "slot": "100", "entry": SignedValidatorRegistration::default() },
+                { "slot": "140", "entry": SignedValidatorRegistration::default() },
+            ])))
+            .mount(&server)
+            .await;
+
+        // A failing relay is skipped.
+        let failing = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(400))
+            .mount(&failing)
+            .await;
+
+        let client = super::Client::with_relays([
+            mock_relay("mock", &server),
+            mock_relay("failing", &failing),
+        ])
+        .with_chain_spec(spec.clone());
+        let registrations = client
+            .get_validator_registration_for_all_slots_on_all_relays()
+            .await?;
+
+        let mut slots: Vec<_> = registrations.keys().copied().collect();
+        slots.sort();
+        assert_eq!(slots, (96..160).collect::<Vec<_>>());
+        assert_eq!(registrations[&100], vec!["mock".to_string()]);
+        assert_eq!(registrations[&140], vec!["mock".to_string()]);
+        assert!(registrations[&96].is_empty());
+
+        // Without registrations, the slots come from the clock, which is before genesis.
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .mount(&server)
+            .await;
+
+        let client =
+            super::Client::with_relays([mock_relay("mock", &server)]).with_chain_spec(spec);
+        let registrations = client
+            .get_validator_registration_for_all_slots_on_all_relays()
+            .await?;
+        assert!(registrations.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_submit_blinded_block() -> anyhow::Result<()> {
+        let (block, payload) = deneb_blinded_block(Hash32::repeat_byte(0x42));
+        let payload = GetPayloadResponse::Deneb(payload);
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(constants::SUBMIT_BLINDED_BLOCK))
+            .and(header(constants::CONSENSUS_VERSION_HEADER, "deneb"))
+            .and(body_json(&block))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&payload))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = super::Client::with_relays([mock_relay("mock", &server)]);
+        let response = client.submit_blinded_block("mock", &block).await?;
+
+        assert_eq!(response, payload);
+        assert_eq!(response.block_hash(), block.block_hash());
+        assert!(response.blobs_bundle().is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_submit_blinded_block_payload_mismatch() -> anyhow::Result<()> {
+        let (block, payload) = deneb_blinded_block(Hash32::repeat_byte(0x42));
+        let mut other_block_payload = payload.clone();
+        other_block_payload.execution_payload.block_hash = Hash32::repeat_byte(0x43);
+
+        for response in [
+            GetPayloadResponse::Electra(payload),
+            GetPayloadResponse::Deneb(other_block_payload),
+        ] {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path(constants::SUBMIT_BLINDED_BLOCK))
+                .respond_with(ResponseTemplate::new(200).set_body_json(&response))
+                .mount(&server)
+                .await;
+
+            let client = super::Client::with_relays([mock_relay("mock", &server)]);
+            let err = client
+                .submit_blinded_block("mock", &block)
+                .await
+                .unwrap_err();
+            assert!(matches!(err, Error::PayloadMismatch { .. }), "{err}");
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_submit_block() -> anyhow::Result<()> {
+        let request = SubmitBlockRequest::Deneb(SubmitBlockRequestDeneb {
+            message: BidTrace {
+                slot: 1,
+                value: Wei::from_gwei(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(constants::SUBMIT_BLOCK))
+            .and(query_param("cancellations", "1"))
+            .and(header(constants::CONSENSUS_VERSION_HEADER, "deneb"))
+            .and(header("content-type", "application/octet-stream"))
+            .and(header("content-encoding", "gzip"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = super::Client::with_relays([mock_relay("mock", &server)]);
+        let options = SubmitBlockOptions {
+            cancellations: true,
+            encoding: SubmissionEncoding::Ssz,
+            gzip: true,
+        };
+        client.submit_block("mock", &request, &options).await?;
+
+        let received = server.received_requests().await.unwrap();
+        let mut body = Vec::new();
+        GzDecoder::new(received[0].body.as_slice()).read_to_end(&mut body)?;
+        assert_eq!(body, request.as_ssz_bytes());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_submit_block_malformed_blobs_bundle() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+        let client = super::Client::with_relays([mock_relay("mock", &server)]);
+
+        let blob = Bytes::from(vec![0; BYTES_PER_BLOB]);
+        for blobs_bundle in [
+            // A blob without a proof.
+            BlobsBundle {
+                commitments: vec![Default::default()],
+                proofs: vec![],
+                blobs: vec![blob.clone()],
+            },
+            // A truncated blob.
+            BlobsBundle {
+                commitments: vec![Default::default()],
+                proofs: vec![Default::default()],
+                blobs: vec![blob.slice(1..)],
+            },
+        ] {
+            let request = SubmitBlockRequest::Deneb(SubmitBlockRequestDeneb {
+                blobs_bundle,
+                ..Default::default()
+            });
+            let err = client
+                .submit_block("mock", &request, &SubmitBlockOptions::default())
+                .await
+                .unwrap_err();
+            assert!(matches!(err, Error::Serialize(_)), "{err}");
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_registration_verification() -> anyhow::Result<()> {
+        let valid = signed_registration(&secret_key(1), constants::MAINNET_GENESIS_FORK_VERSION);
+        let mut invalid =
+            signed_registration(&secret_key(2), constants::MAINNET_GENESIS_FORK_VERSION);
+        invalid.message.gas_limit += 1;
+
+        let server = MockServer::start().await;
+        for registration in [&valid, &invalid] {
+            Mock::given(method("GET"))
+                .and(path(constants::CHECK_VALIDATOR_REGISTRATION))
+                .and(query_param(
+                    "pubkey",
+                    registration.message.pubkey.to_string(),
+                ))
+                .respond_with(ResponseTemplate::new(200).set_body_json(registration))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path(constants::GET_VALIDATORS_ENDPOINT))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "slot": "1", "validator_index": "10", "entry": valid },
+                { "slot": "2", "validator_index": "20", "entry": invalid },
+            ])))
+            .mount(&server)
+            .await;
+
+        // Registrations are not verified by default.
+        let client = super::Client::with_relays([mock_relay("mock", &server)]);
+        client
+            .get_validator_registration("mock", &invalid.message.pubkey)
+            .await?;
+        assert_eq!(
+            client
+                .get_validators_for_current_and_next_epoch("mock")
+                .await?
+                .len(),
+            2
+        );
+
+        let client = client.with_registration_verification(true);
+        let registration = client
+            .get_validator_registration("mock", &valid.message.pubkey)
+            .await?;
+        assert_eq!(registration, valid);
+
+        let err = client
+            .get_validator_registration("mock", &invalid.message.pubkey)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidRegistration { pubkey, .. } if pubkey == invalid.message.pubkey.to_string())
+        );
+
+        // Invalid registrations are left out of the schedule of the relay.
+        let validators = client
+            .get_validators_for_current_and_next_epoch("mock")
+            .await?;
+        assert_eq!(validators.len(), 1);
+        assert_eq!(validators[0].entry, valid);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_on_all_relays_timeout() -> anyhow::Result<()> {
+        let fast = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&fast)
+            .await;
+
+        let slow = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("[]")
+                    .set_delay(std::time::Duration::from_secs(5)),
+            )
+            .mount(&slow)
+            .await;
+
+        let client =
+            super::Client::with_relays([mock_relay("fast", &fast), mock_relay("slow", &slow)])
+                .with_relay_timeout(std::time::Duration::from_millis(200));
+
+        let start = std::time::Instant::now();
+        let response = client
+            .get_payloads_delivered_bidtraces_on_all_relays(&Default::default())
+            .await?;
+
+        assert!(start.elapsed() < std::time::Duration::from_secs(2));
+        assert!(response.contains_key("fast"));
+        assert!(!response.contains_key("slow"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_all_relays_outcomes() -> anyhow::Result<()> {
+        let up = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&up)
+            .await;
+
+        let down = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&down)
+            .await;
+
+        let client = super::Client::with_relays([mock_relay("up", &up), mock_relay("down", &down)]);
+
+        let opts = Default::default();
+        let responses = client
+            .query_all_relays(|relay| client.get_payload_delivered_bidtraces(relay, &opts))
+            .await;
+
+        let summary = responses.summary();
+        assert_eq!(
+            (summary.total, summary.succeeded, summary.failed),
+            (2, 1, 1)
+        );
+        assert!(responses
+            .successes()
+            .all(|(relay, data)| relay == "up" && data.is_empty()));
+        assert!(responses.failures().all(|(relay, _)| relay == "down"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_client_builder_headers() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("user-agent", "test-agent/1.0"))
+            .and(header("x-api-key", "secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&server)
+            .await;
+
+        let client = super::ClientBuilder::with_relays([mock_relay("mock", &server)])
+            .user_agent("test-agent/1.0")
+            .default_header(
+                reqwest::header::HeaderName::from_static("x-api-key"),
+                reqwest::header::HeaderValue::from_static("secret"),
+            )
+            .timeout(std::time::Duration::from_secs(1))
+            .build()?;
+
+        let response = client
+            .get_payload_delivered_bidtraces("mock", &Default::default())
+            .await?;
+
+        assert!(response.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_errors() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+
+        let client = super::Client::with_relays([mock_relay("mock", &server)]).with_retry_policy(
+            RetryPolicy {
+                base_backoff: std::time::Duration::from_millis(10),
+                ..Default::default()
+            },
+        );
+
+        let response = client
+            .get_payload_delivered_bidtraces("mock", &Default::default())
+            .await?;
+
+        assert!(response.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_submissions_not_retried_by_default() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = super::Client::with_relays([mock_relay("mock", &server)]);
+        let request = SubmitBlockRequest::Deneb(Default::default());
+        let err = client
+            .submit_block("mock", &request, &Default::default())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), Some(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+
+        let err = client.register_validators("mock", &[]).await.unwrap_err();
+        assert_eq!(err.status(), Some(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_submission_retry_policy() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .expect(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+
+        let client = super::Client::with_relays([mock_relay("mock", &server)])
+            .with_submission_retry_policy(RetryPolicy {
+                base_backoff: std::time::Duration::from_millis(10),
+                ..Default::default()
+            });
+
+        client.register_validators("mock", &[]).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retry_honors_retry_after() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "1"))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+
+        let client = super::Client::with_relays([mock_relay("mock", &server)]);
+
+        let start = std::time::Instant::now();
+        client
+            .get_payload_delivered_bidtraces("mock", &Default::default())
+            .await?;
+
+        assert!(start.elapsed() >= std::time::Duration::from_secs(1));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_no_retry_on_client_errors() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(400))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = super::Client::with_relays([mock_relay("mock", &server)]);
+        let err = client
+            .get_payload_delivered_bidtraces("mock", &Default::default())
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.status(), Some(reqwest::StatusCode::BAD_REQUEST));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_applied_in_fetch() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let client = super::Client::with_relays([mock_relay("mock", &server)])
+            .with_rate_limit("mock", Some(RateLimit::new(10.0, 1)?));
+
+        let start = std::time::Instant::now();
+        for _ in 0..3 {
+            client
+                .get_payload_delivered_bidtraces("mock", &Default::default())
+                .await?;
+        }
+
+        assert!(start.elapsed() >= std::time::Duration::from_millis(190));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_add_and_remove_relay() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&server)
+            .await;
+
+        let mut client = super::Client::with_relays([]);
+        let err = client
+            .get_payload_delivered_bidtraces("mock", &Default::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::UnknownRelay(name) if name == "mock"));
+
+        client.add_relay(mock_relay("mock", &server));
+        assert!(client.contains("mock"));
+        client
+            .get_payload_delivered_bidtraces("mock", &Default::default())
+            .await?;
+
+        assert!(client.remove_relay("mock").is_some());
+        assert!(!client.contains("mock"));
+        Ok(())
+    }
 }
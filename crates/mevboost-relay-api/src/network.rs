@@ -0,0 +1,163 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use crate::{constants, primitives::ForkVersion, ChainSpec, Error, RateLimit, Relay, Result};
+
+/// An Ethereum network with built-in relay presets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Network {
+    /// Ethereum mainnet.
+    #[default]
+    Mainnet,
+    /// The Holesky testnet.
+    Holesky,
+    /// The Sepolia testnet.
+    Sepolia,
+    /// The Hoodi testnet.
+    Hoodi,
+}
+
+impl Network {
+    /// All built-in networks.
+    pub const ALL: [Self; 4] = [Self::Mainnet, Self::Holesky, Self::Sepolia, Self::Hoodi];
+
+    /// Returns the relay URLs of the network, by relay name.
+    pub fn relay_urls(&self) -> &'static HashMap<&'static str, &'static str> {
+        match self {
+            Self::Mainnet => &constants::DEFAULT_RELAYS,
+            Self::Holesky => &constants::HOLESKY_RELAYS,
+            Self::Sepolia => &constants::SEPOLIA_RELAYS,
+            Self::Hoodi => &constants::HOODI_RELAYS,
+        }
+    }
+
+    /// Returns the default client-side rate limits of the relays of the network, by relay name.
+    pub fn rate_limits(&self) -> &'static HashMap<&'static str, RateLimit> {
+        match self {
+            Self::Mainnet => &constants::DEFAULT_RATE_LIMITS,
+            Self::Holesky => &constants::HOLESKY_RATE_LIMITS,
+            Self::Sepolia => &constants::SEPOLIA_RATE_LIMITS,
+            Self::Hoodi => &constants::HOODI_RATE_LIMITS,
+        }
+    }
+
+    /// Returns the relays of the network, with their default [`Network::rate_limits()`].
+    pub fn relays(&self) -> Vec<Relay> {
+        let rate_limits = self.rate_limits();
+
+        self.relay_urls()
+            .iter()
+            .map(|(name, url)| {
+                let relay = Relay::new(*name, url).expect("Built-in relay URLs are valid");
+                match rate_limits.get(name) {
+                    Some(limit) => relay.with_rate_limit(*limit),
+                    None => relay,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the genesis time of the network, in seconds since the Unix epoch.
+    pub fn genesis_time(&self) -> u64 {
+        match self {
+            Self::Mainnet => constants::MAINNET_GENESIS_TIME,
+            Self::Holesky => constants::HOLESKY_GENESIS_TIME,
+            Self::Sepolia => constants::SEPOLIA_GENESIS_TIME,
+            Self::Hoodi => constants::HOODI_GENESIS_TIME,
+        }
+    }
+
+    /// Returns the genesis fork version of the network, used to verify signatures.
+    pub fn genesis_fork_version(&self) -> ForkVersion {
+        match self {
+            Self::Mainnet => constants::MAINNET_GENESIS_FORK_VERSION,
+            Self::Holesky => constants::HOLESKY_GENESIS_FORK_VERSION,
+            Self::Sepolia => constants::SEPOLIA_GENESIS_FORK_VERSION,
+            Self::Hoodi => constants::HOODI_GENESIS_FORK_VERSION,
+        }
+    }
+
+    /// Returns the number of slots per epoch of the network.
+    pub fn slots_per_epoch(&self) -> u64 {
+        constants::SLOTS_PER_EPOCH
+    }
+
+    /// Returns the chain spec of the network.
+    pub fn chain_spec(&self) -> ChainSpec {
+        let (capella_fork_epoch, deneb_fork_epoch, electra_fork_epoch) = match self {
+            Self::Mainnet => (194_048, 269_568, 364_032),
+            Self::Holesky => (256, 29_696, 115_968),
+            Self::Sepolia => (56_832, 132_608, 222_464),
+            Self::Hoodi => (0, 0, 2_048),
+        };
+
+        ChainSpec {
+            config_name: Some(self.to_string()),
+            genesis_time: self.genesis_time(),
+            genesis_fork_version: self.genesis_fork_version(),
+            seconds_per_slot: constants::SECONDS_PER_SLOT,
+            slots_per_epoch: self.slots_per_epoch(),
+            capella_fork_epoch,
+            deneb_fork_epoch,
+            electra_fork_epoch,
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mainnet => write!(f, "mainnet"),
+            Self::Holesky => write!(f, "holesky"),
+            Self::Sepolia => write!(f, "sepolia"),
+            Self::Hoodi => write!(f, "hoodi"),
+        }
+    }
+}
+
+impl FromStr for Network {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|network| network.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| Error::UnknownNetwork(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Network;
+    use crate::{constants, Error};
+
+    #[test]
+    fn test_network_from_str() {
+        for network in Network::ALL {
+            assert_eq!(network.to_string().parse::<Network>().unwrap(), network);
+        }
+        assert_eq!("Hoodi".parse::<Network>().unwrap(), Network::Hoodi);
+        assert!(matches!(
+            "goerli".parse::<Network>(),
+            Err(Error::UnknownNetwork(_))
+        ));
+    }
+
+    #[test]
+    fn test_network_relays() {
+        for network in Network::ALL {
+            let relays = network.relays();
+            assert_eq!(relays.len(), network.relay_urls().len(), "{network}");
+            assert_eq!(network.rate_limits().len(), relays.len(), "{network}");
+            assert!(
+                relays
+                    .iter()
+                    .all(|relay| relay.options.rate_limit
+                        == Some(constants::DEFAULT_RELAY_RATE_LIMIT))
+            );
+        }
+        assert_eq!(
+            Network::Hoodi.genesis_fork_version(),
+            constants::HOODI_GENESIS_FORK_VERSION
+        );
+    }
+}
@@ -0,0 +1,464 @@
+use std::{
+    collections::HashSet,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{
+    ready,
+    stream::{self, BoxStream, StreamExt, TryStreamExt},
+    Stream,
+};
+
+use crate::{
+    constants,
+    primitives::Hash32,
+    types::{
+        BuilderBidsReceivedOptions, BuilderBlockBidtrace, PayloadBidtrace,
+        PayloadDeliveredQueryOptions,
+    },
+    Client, Error, MultiRelayResponse, Result,
+};
+
+/// Position of a delivered payloads stream.
+#[derive(Debug)]
+struct PayloadCursor {
+    /// Highest slot of the next page, or `None` once the stream is exhausted.
+    cursor: Option<u64>,
+    /// Payloads already yielded at the `cursor` slot, which the next page returns again.
+    seen: HashSet<(u64, Hash32)>,
+}
+
+/// Stream of the builder bids received by relays over a slot range, returned by
+/// [`Client::stream_builder_blocks_received()`].
+///
+/// Yields `(slot, responses)` in slot order, and keeps track of the last slot it yielded
+/// so that an interrupted range can be resumed from [`BuilderBlocksStream::resume_slot()`].
+/// The stream is `Send`, so that backfills can run it in a spawned task.
+pub struct BuilderBlocksStream<'a> {
+    /// Slot queries, yielded in slot order.
+    inner: BoxStream<'a, (u64, MultiRelayResponse<Vec<BuilderBlockBidtrace>>)>,
+    /// First slot of the range.
+    from_slot: u64,
+    /// Last slot of the range.
+    to_slot: u64,
+    /// Last slot yielded, for which every relay has answered.
+    last_completed_slot: Option<u64>,
+}
+
+impl BuilderBlocksStream<'_> {
+    /// Returns the last slot yielded by the stream, or `None` if no slot was yielded yet.
+    ///
+    /// Every slot up to it has been yielded with the outcome of every relay, while later
+    /// slots may have been queried but were not yielded.
+    pub fn last_completed_slot(&self) -> Option<u64> {
+        self.last_completed_slot
+    }
+
+    /// Returns the first slot that has not been yielded yet, or `None` once the whole range
+    /// has been yielded. Passing it as `from_slot` to [`Client::stream_builder_blocks_received()`]
+    /// resumes the range without skipping or repeating slots.
+    pub fn resume_slot(&self) -> Option<u64> {
+        match self.last_completed_slot {
+            Some(slot) => slot.checked_add(1).filter(|slot| *slot <= self.to_slot),
+            None => (self.from_slot <= self.to_slot).then_some(self.from_slot),
+        }
+    }
+}
+
+impl std::fmt::Debug for BuilderBlocksStream<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BuilderBlocksStream")
+            .field("from_slot", &self.from_slot)
+            .field("to_slot", &self.to_slot)
+            .field("last_completed_slot", &self.last_completed_slot)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for BuilderBlocksStream<'_> {
+    type Item = (u64, MultiRelayResponse<Vec<BuilderBlockBidtrace>>);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = ready!(self.inner.as_mut().poll_next(cx));
+        if let Some((slot, _)) = &item {
+            self.last_completed_slot = Some(*slot);
+        }
+        Poll::Ready(item)
+    }
+}
+
+impl Client {
+    /// Stream the payloads delivered by the relay with the given name, from `to_slot`
+    /// down to `from_slot` (both inclusive), most recent first.
+    ///
+    /// Pages are fetched lazily by walking the `cursor` of the delivered payloads query
+    /// backwards. Payloads returned twice at page boundaries are only yielded once.
+    /// The stream ends after the first error.
+    ///
+    /// ```no_run
+    /// # async fn example() -> mevboost_relay_api::Result<()> {
+    /// use futures::TryStreamExt;
+    ///
+    /// let client = mevboost_relay_api::Client::default();
+    /// let payloads: Vec<_> = client
+    ///     .stream_payload_delivered_bidtraces("flashbots", 8_000_000, 8_001_000)
+    ///     .try_collect()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream_payload_delivered_bidtraces<'a>(
+        &'a self,
+        relay_name: &'a str,
+        from_slot: u64,
+        to_slot: u64,
+    ) -> impl Stream<Item = Result<PayloadBidtrace>> + 'a {
+        let start = PayloadCursor {
+            cursor: (from_slot <= to_slot).then_some(to_slot),
+            seen: HashSet::new(),
+        };
+
+        stream::try_unfold(start, move |mut state| async move {
+            let Some(cursor) = state.cursor else {
+                return Ok::<_, Error>(None);
+            };
+
+            let opts = PayloadDeliveredQueryOptions {
+                cursor: Some(cursor),
+                limit: Some(constants::PAYLOAD_DELIVERED_PAGE_LIMIT),
+                ..Default::default()
+            };
+            let page = self
+                .get_payload_delivered_bidtraces(relay_name, &opts)
+                .await?;
+
+            let page_len = page.len() as u64;
+            let lowest_slot = page.iter().map(|payload| payload.bid_trace.slot).min();
+
+            let payloads: Vec<_> = page
+                .into_iter()
+                .filter(|payload| (from_slot..=to_slot).contains(&payload.bid_trace.slot))
+                .filter(|payload| {
+                    !state
+                        .seen
+                        .contains(&(payload.bid_trace.slot, payload.bid_trace.block_hash))
+                })
+                .collect();
+
+            state.cursor = match lowest_slot {
+                // A short page means there is nothing left before it.
+                _ if page_len < constants::PAYLOAD_DELIVERED_PAGE_LIMIT => None,
+                Some(slot) if slot < from_slot => None,
+                // The whole page was at the cursor slot: skip it to guarantee progress.
+                Some(slot) if slot >= cursor => cursor.checked_sub(1),
+                // Query the lowest slot again, as the page may have cut through it.
+                Some(slot) => Some(slot),
+                None => None,
+            };
+
+            state.seen = payloads
+                .iter()
+                .filter(|payload| Some(payload.bid_trace.slot) == state.cursor)
+                .map(|payload| (payload.bid_trace.slot, payload.bid_trace.block_hash))
+                .collect();
+
+            Ok(Some((payloads, state)))
+        })
+        .map_ok(|payloads| stream::iter(payloads.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
+    /// Stream the builder bids received by the given relays for every slot from `from_slot`
+    /// to `to_slot` (both inclusive), in slot order.
+    ///
+    /// The builder blocks received query only filters by a single slot, so every slot is
+    /// queried separately on each relay. Slots are queried concurrently, keeping at most
+    /// `max_concurrency` requests in flight.
+    ///
+    /// Every item holds a slot and the outcome of each relay for that slot. A relay failing
+    /// for a slot does not end the stream: its error is reported in the response of the slot,
+    /// so that only the failed relays and slots need to be queried again.
+    ///
+    /// If the stream is interrupted, the range can be resumed from
+    /// [`BuilderBlocksStream::resume_slot()`]: slots queried but not yielded yet are queried again.
+    ///
+    /// ```no_run
+    /// # async fn example() {
+    /// use futures::StreamExt;
+    ///
+    /// let client = mevboost_relay_api::Client::default();
+    /// let relays = ["flashbots", "ultrasound"];
+    /// let mut slots = client.stream_builder_blocks_received(&relays, 8_000_000, 8_000_100);
+    /// while let Some((slot, responses)) = slots.next().await {
+    ///     for (relay, bids) in responses.successes() {
+    ///         println!("{} bids for slot {} on {}", bids.len(), slot, relay);
+    ///     }
+    ///     for (relay, err) in responses.failures() {
+    ///         println!("Failed to get bids for slot {} on {}: {}", slot, relay, err);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn stream_builder_blocks_received<'a>(
+        &'a self,
+        relay_names: &'a [&'a str],
+        from_slot: u64,
+        to_slot: u64,
+    ) -> BuilderBlocksStream<'a> {
+        // Every slot sends one request per relay.
+        let concurrency = (self.max_concurrency / relay_names.len().max(1)).max(1);
+
+        let inner = stream::iter(from_slot..=to_slot)
+            .map(move |slot| async move {
+                let opts = BuilderBidsReceivedOptions {
+                    slot: Some(slot),
+                    ..Default::default()
+                };
+                let responses = self
+                    .query_relays(relay_names.iter().copied(), |relay_name| {
+                        self.get_builder_blocks_received(relay_name, &opts)
+                    })
+                    .await;
+
+                (slot, responses)
+            })
+            .buffered(concurrency)
+            .boxed();
+
+        BuilderBlocksStream {
+            inner,
+            from_slot,
+            to_slot,
+            last_completed_slot: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{StreamExt, TryStreamExt};
+    use serde_json::json;
+    use wiremock::{
+        matchers::{method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::BuilderBlocksStream;
+    use crate::{constants, tests::mock_relay, Client};
+
+    /// Helper function to create a delivered payload JSON object for the given slot.
+    fn payload(slot: u64) -> serde_json::Value {
+        json!({
+            "slot": slot.to_string(),
+            "parent_hash": format!("0x{:064x}", slot - 1),
+            "block_hash": format!("0x{:064x}", slot),
+            "builder_pubkey": format!("0x{}", "ab".repeat(48)),
+            "proposer_pubkey": format!("0x{}", "cd".repeat(48)),
+            "proposer_fee_recipient": format!("0x{}", "ef".repeat(20)),
+            "gas_limit": "30000000",
+            "gas_used": "15000000",
+            "value": "1000000000000000000",
+            "num_tx": "100",
+            "block_number": slot.to_string(),
+        })
+    }
+
+    /// Helper function to create a builder bid JSON object for the given slot.
+    fn bid(slot: u64, timestamp_ms: u64) -> serde_json::Value {
+        let mut bid = payload(slot);
+        bid["timestamp_ms"] = timestamp_ms.to_string().into();
+        bid
+    }
+
+    /// Helper function to mount a page of payloads for the given cursor, in descending slot order.
+    async fn mount_page(
+        server: &MockServer,
+        cursor: u64,
+        slots: impl DoubleEndedIterator<Item = u64>,
+    ) {
+        let page: Vec<_> = slots.rev().map(payload).collect();
+        Mock::given(method("GET"))
+            .and(path(constants::GET_DELIVERED_PAYLOADS))
+            .and(query_param("cursor", cursor.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_stream_payloads_across_pages() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        // Full pages of 200 payloads, overlapping at slot 801.
+        mount_page(&server, 1000, 801..=1000).await;
+        mount_page(&server, 801, 602..=801).await;
+
+        let client = Client::with_relays([mock_relay("mock", &server)]);
+        let payloads: Vec<_> = client
+            .stream_payload_delivered_bidtraces("mock", 700, 1000)
+            .try_collect()
+            .await?;
+
+        let slots: Vec<_> = payloads
+            .iter()
+            .map(|payload| payload.bid_trace.slot)
+            .collect();
+        assert_eq!(slots, (700..=1000).rev().collect::<Vec<_>>());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stream_payloads_stops_at_last_page() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        mount_page(&server, 1000, 801..=1000).await;
+        // A short page: the relay has no payloads before slot 750.
+        mount_page(&server, 801, 750..=801).await;
+
+        let client = Client::with_relays([mock_relay("mock", &server)]);
+        let payloads: Vec<_> = client
+            .stream_payload_delivered_bidtraces("mock", 0, 1000)
+            .try_collect()
+            .await?;
+
+        assert_eq!(payloads.len(), 251);
+        assert_eq!(
+            payloads.last().map(|payload| payload.bid_trace.slot),
+            Some(750)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stream_payloads_error() {
+        let client = Client::with_relays([]);
+        let result: Result<Vec<_>, _> = client
+            .stream_payload_delivered_bidtraces("unknown", 0, 10)
+            .try_collect()
+            .await;
+
+        assert!(matches!(result, Err(crate::Error::UnknownRelay(_))));
+    }
+
+    #[tokio::test]
+    async fn test_stream_builder_blocks_across_relays() -> anyhow::Result<()> {
+        let relay_a = MockServer::start().await;
+        let relay_b = MockServer::start().await;
+        for slot in 1..=5 {
+            for (server, timestamp) in [(&relay_a, 1), (&relay_b, 2)] {
+                Mock::given(method("GET"))
+                    .and(path(constants::GET_BUILDER_BLOCKS_RECEIVED))
+                    .and(query_param("slot", slot.to_string()))
+                    .respond_with(ResponseTemplate::new(200).set_body_json([bid(slot, timestamp)]))
+                    .expect(1)
+                    .mount(server)
+                    .await;
+            }
+        }
+
+        let client = Client::with_relays([mock_relay("a", &relay_a), mock_relay("b", &relay_b)])
+            .with_max_concurrency(4);
+        let slots: Vec<_> = client
+            .stream_builder_blocks_received(&["a", "b"], 1, 5)
+            .collect()
+            .await;
+
+        assert_eq!(
+            slots.iter().map(|(slot, _)| *slot).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        for (slot, responses) in slots {
+            let bids = responses.into_successes();
+            assert_eq!(bids.len(), 2);
+            for (relay, timestamp) in [("a", 1), ("b", 2)] {
+                assert_eq!(bids[relay].len(), 1);
+                assert_eq!(bids[relay][0].timestamp_ms, timestamp);
+                assert_eq!(bids[relay][0].payload.bid_trace.slot, slot);
+            }
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stream_builder_blocks_continues_after_relay_error() -> anyhow::Result<()> {
+        let failing = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("slot", "3"))
+            .respond_with(ResponseTemplate::new(400))
+            .with_priority(1)
+            .mount(&failing)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&failing)
+            .await;
+
+        let healthy = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&healthy)
+            .await;
+
+        let client = Client::with_relays([
+            mock_relay("failing", &failing),
+            mock_relay("healthy", &healthy),
+        ]);
+        let slots: Vec<_> = client
+            .stream_builder_blocks_received(&["failing", "healthy"], 1, 5)
+            .collect()
+            .await;
+
+        assert_eq!(slots.len(), 5);
+        for (slot, responses) in slots {
+            assert!(responses.get("healthy").unwrap().is_ok());
+            assert_eq!(responses.get("failing").unwrap().is_ok(), slot != 3);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stream_builder_blocks_resume() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        for slot in 1..=6 {
+            Mock::given(method("GET"))
+                .and(query_param("slot", slot.to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json([bid(slot, 1)]))
+                .mount(&server)
+                .await;
+        }
+
+        let client = Client::with_relays([mock_relay("mock", &server)]).with_max_concurrency(4);
+        let mut stream = client.stream_builder_blocks_received(&["mock"], 1, 6);
+        assert_eq!(stream.last_completed_slot(), None);
+        assert_eq!(stream.resume_slot(), Some(1));
+
+        // Interrupt the range after two slots, while later slots are in flight.
+        let mut slots = Vec::new();
+        for _ in 0..2 {
+            let (slot, _) = stream.next().await.unwrap();
+            slots.push(slot);
+        }
+        assert_eq!(stream.last_completed_slot(), Some(2));
+        let resume_slot = stream.resume_slot().unwrap();
+        drop(stream);
+
+        let mut stream = client.stream_builder_blocks_received(&["mock"], resume_slot, 6);
+        while let Some((slot, responses)) = stream.next().await {
+            assert_eq!(
+                responses.into_successes()["mock"][0].payload.bid_trace.slot,
+                slot
+            );
+            slots.push(slot);
+        }
+        assert_eq!(slots, (1..=6).collect::<Vec<_>>());
+        assert_eq!(stream.last_completed_slot(), Some(6));
+        assert_eq!(stream.resume_slot(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_blocks_stream_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<BuilderBlocksStream<'static>>();
+    }
+}
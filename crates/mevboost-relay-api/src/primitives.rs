@@ -0,0 +1,285 @@
+use std::{fmt, str::FromStr};
+
+use alloy_primitives::FixedBytes;
+use serde::{Deserialize, Serialize};
+
+pub use alloy_primitives::{Address, Bloom, Bytes, B256, U256};
+
+/// A 48-byte BLS public key, as used by validators, builders and relays.
+pub type BlsPublicKey = FixedBytes<48>;
+
+/// A 96-byte BLS signature.
+pub type BlsSignature = FixedBytes<96>;
+
+/// A 32-byte hash, such as a block hash.
+pub type Hash32 = B256;
+
+/// A 4-byte fork version, such as the genesis fork version of a network.
+pub type ForkVersion = FixedBytes<4>;
+
+/// A 48-byte KZG commitment to a blob.
+pub type KzgCommitment = FixedBytes<48>;
+
+/// A 48-byte KZG proof of a blob.
+pub type KzgProof = FixedBytes<48>;
+
+/// Number of wei in one gwei.
+const WEI_PER_GWEI: u64 = 1_000_000_000;
+
+/// Number of wei in one ether.
+const WEI_PER_ETH: u64 = 1_000_000_000_000_000_000;
+
+/// An amount of wei, such as the value of a bid.
+///
+/// Displayed and (de)serialized as an exact decimal number of wei. Use [`Wei::format_eth()`]
+/// for an exact amount of ETH, or [`Wei::as_eth()`] for an approximate one.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Wei(#[serde(with = "quoted_u256")] pub U256);
+
+impl Wei {
+    /// Zero wei.
+    pub const ZERO: Self = Self(U256::ZERO);
+
+    /// Create an amount from a number of gwei.
+    pub fn from_gwei(gwei: u64) -> Self {
+        Self(U256::from(gwei) * U256::from(WEI_PER_GWEI))
+    }
+
+    /// Returns the amount as a [`U256`] number of wei.
+    pub fn into_inner(self) -> U256 {
+        self.0
+    }
+
+    /// Checked addition. Returns `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// Checked subtraction. Returns `None` if `other` is larger than `self`.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    /// Checked multiplication by a scalar. Returns `None` on overflow.
+    pub fn checked_mul(self, factor: u64) -> Option<Self> {
+        self.0.checked_mul(U256::from(factor)).map(Self)
+    }
+
+    /// Checked sum of the given amounts. Returns `None` on overflow.
+    pub fn checked_sum(amounts: impl IntoIterator<Item = Self>) -> Option<Self> {
+        amounts
+            .into_iter()
+            .try_fold(Self::ZERO, |total, amount| total.checked_add(amount))
+    }
+
+    /// Returns the amount in gwei. Lossy for large amounts.
+    pub fn as_gwei(&self) -> f64 {
+        self.as_unit(WEI_PER_GWEI)
+    }
+
+    /// Returns the amount in ETH. Lossy for large or very precise amounts.
+    pub fn as_eth(&self) -> f64 {
+        self.as_unit(WEI_PER_ETH)
+    }
+
+    /// Returns the exact amount in ETH as a decimal string, without trailing zeros.
+    pub fn format_eth(&self) -> String {
+        let (eth, rem) = self.0.div_rem(U256::from(WEI_PER_ETH));
+        if rem.is_zero() {
+            return eth.to_string();
+        }
+
+        let decimals = format!("{:018}", rem);
+        format!("{}.{}", eth, decimals.trim_end_matches('0'))
+    }
+
+    /// Helper function to convert the amount to a unit worth `wei_per_unit` wei,
+    /// keeping the precision of the fractional part.
+    fn as_unit(&self, wei_per_unit: u64) -> f64 {
+        let (units, rem) = self.0.div_rem(U256::from(wei_per_unit));
+        f64::from(units) + f64::from(rem) / wei_per_unit as f64
+    }
+}
+
+impl From<U256> for Wei {
+    fn from(value: U256) -> Self {
+        Self(value)
+    }
+}
+
+impl From<u64> for Wei {
+    fn from(value: u64) -> Self {
+        Self(U256::from(value))
+    }
+}
+
+impl From<Wei> for U256 {
+    fn from(value: Wei) -> Self {
+        value.0
+    }
+}
+
+impl FromStr for Wei {
+    type Err = alloy_primitives::ruint::ParseError;
+
+    /// Parses a decimal number of wei.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        U256::from_str_radix(s, 10).map(Self)
+    }
+}
+
+impl fmt::Display for Wei {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// (De)serialize a `u64` as a quoted decimal string, as used by the consensus and builder APIs.
+/// Unquoted numbers are accepted when deserializing.
+pub(crate) mod quoted_u64 {
+    use serde::{Deserializer, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        serde_aux::prelude::deserialize_number_from_string(deserializer)
+    }
+}
+
+/// (De)serialize a list of `u64` as quoted decimal strings, as used by the consensus APIs.
+pub(crate) mod quoted_u64_vec {
+    use serde::{ser::SerializeSeq, Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    struct Quoted(#[serde(with = "super::quoted_u64")] u64);
+
+    pub(crate) fn serialize<S: Serializer>(
+        values: &[u64],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+        for value in values {
+            seq.serialize_element(&value.to_string())?;
+        }
+        seq.end()
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u64>, D::Error> {
+        let values = Vec::<Quoted>::deserialize(deserializer)?;
+        Ok(values.into_iter().map(|Quoted(value)| value).collect())
+    }
+}
+
+/// (De)serialize a [`U256`] as a quoted decimal string, as used by the relay APIs.
+pub(crate) mod quoted_u256 {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use super::U256;
+
+    pub(crate) fn serialize<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<U256, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        U256::from_str_radix(&value, 10).map_err(Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::{Address, Wei, U256};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Value {
+        #[serde(with = "super::quoted_u256")]
+        value: U256,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Slot {
+        #[serde(with = "super::quoted_u64")]
+        slot: u64,
+    }
+
+    #[test]
+    fn test_quoted_u64() {
+        let slot: Slot = serde_json::from_str(r#"{"slot":"42"}"#).unwrap();
+        assert_eq!(slot, Slot { slot: 42 });
+        assert_eq!(serde_json::to_string(&slot).unwrap(), r#"{"slot":"42"}"#);
+        assert_eq!(
+            serde_json::from_str::<Slot>(r#"{"slot":42}"#).unwrap(),
+            slot
+        );
+    }
+
+    #[test]
+    fn test_quoted_u256() {
+        let json = r#"{"value":"123456789012345678901234567890"}"#;
+        let value: Value = serde_json::from_str(json).unwrap();
+        assert_eq!(value.value, U256::from(123456789012345678901234567890u128));
+        assert_eq!(serde_json::to_string(&value).unwrap(), json);
+
+        assert!(serde_json::from_str::<Value>(r#"{"value":"0x10"}"#).is_err());
+        assert!(serde_json::from_str::<Value>(r#"{"value":"-1"}"#).is_err());
+    }
+
+    #[test]
+    fn test_address_checksum_display() {
+        let address: Address = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            address.to_string(),
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        );
+    }
+
+    #[test]
+    fn test_wei_arithmetic() {
+        let a = Wei::from_gwei(1);
+        let b = Wei::from(500_000_000u64);
+
+        assert!(b < a);
+        assert_eq!(a.checked_sub(b), Some(b));
+        assert_eq!(b.checked_sub(a), None);
+        assert_eq!(b.checked_mul(2), Some(a));
+        assert_eq!(Wei(U256::MAX).checked_add(a), None);
+        assert_eq!(Wei::checked_sum([a, b, b]), Some(Wei::from_gwei(2)));
+        assert_eq!(Wei::checked_sum([Wei(U256::MAX), a]), None);
+    }
+
+    #[test]
+    fn test_wei_formatting() {
+        let value: Wei = "15000000000000000000".parse().unwrap();
+        assert_eq!(value.to_string(), "15000000000000000000");
+        assert_eq!(value.format_eth(), "15");
+        assert_eq!(value.as_eth(), 15.0);
+
+        let value = Wei::from(52_345_678_901_234_567u64);
+        assert_eq!(value.format_eth(), "0.052345678901234567");
+        assert_eq!(Wei::from(1u64).format_eth(), "0.000000000000000001");
+        assert_eq!(Wei::from_gwei(3).as_gwei(), 3.0);
+        assert!((value.as_eth() - 0.052345678901234567).abs() < 1e-15);
+
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#""52345678901234567""#
+        );
+        assert_eq!(
+            serde_json::from_str::<Wei>(r#""52345678901234567""#).unwrap(),
+            value
+        );
+    }
+}
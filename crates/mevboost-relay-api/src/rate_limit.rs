@@ -0,0 +1,200 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use tokio::time::Instant;
+
+use crate::{Error, Relay, Result};
+
+/// Token-bucket rate limit for requests to a single relay.
+///
+/// The bucket holds up to `burst` tokens and is refilled at `requests_per_second`.
+/// Every request consumes one token, and waits for the bucket to refill if it is empty.
+/// The token is consumed even if the request then times out or is cancelled while waiting,
+/// so that the limit is never exceeded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// Sustained number of requests per second.
+    requests_per_second: f64,
+    /// Maximum number of requests that can be sent at once after a period of inactivity.
+    burst: u32,
+}
+
+impl RateLimit {
+    /// Create a new rate limit. The burst is at least 1.
+    ///
+    /// Fails with [`Error::InvalidRateLimit`] if `requests_per_second` is not a positive, finite number.
+    pub fn new(requests_per_second: f64, burst: u32) -> Result<Self> {
+        if !(requests_per_second.is_finite() && requests_per_second > 0.0) {
+            return Err(Error::InvalidRateLimit(format!(
+                "requests per second must be positive, got {requests_per_second}"
+            )));
+        }
+
+        Ok(Self::new_unchecked(requests_per_second, burst))
+    }
+
+    /// Create a new rate limit without validating it, for the built-in limits.
+    pub(crate) const fn new_unchecked(requests_per_second: f64, burst: u32) -> Self {
+        Self {
+            requests_per_second,
+            burst: if burst == 0 { 1 } else { burst },
+        }
+    }
+
+    /// Returns the sustained number of requests per second.
+    pub fn requests_per_second(&self) -> f64 {
+        self.requests_per_second
+    }
+
+    /// Returns the maximum number of requests that can be sent at once.
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+}
+
+/// Per-relay token-bucket rate limiter.
+///
+/// Relays are limited by the rate limit in their [`RelayOptions`](crate::RelayOptions),
+/// or by the default rate limit if they have none.
+#[derive(Debug, Default)]
+pub(crate) struct RateLimiter {
+    /// Rate limit applied to relays without a specific limit. `None` means unlimited.
+    default_limit: Option<RateLimit>,
+    /// Token buckets by relay name, created on first use.
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Create a new rate limiter with a default limit for relays without a specific limit.
+    pub(crate) fn new(default_limit: Option<RateLimit>) -> Self {
+        Self {
+            default_limit,
+            buckets: Mutex::default(),
+        }
+    }
+
+    /// Wait until a request to the given relay is allowed by its rate limit.
+    ///
+    /// The token is reserved before waiting, so it stays consumed if the caller is cancelled,
+    /// for example by the relay timeout of a multi-relay query.
+    pub(crate) async fn acquire(&self, relay: &Relay) {
+        let Some(limit) = relay.options.rate_limit.or(self.default_limit) else {
+            return;
+        };
+
+        let wait = {
+            let mut buckets = self.buckets.lock().expect("poisoned");
+            let bucket = buckets
+                .entry(relay.name.clone())
+                .or_insert_with(|| TokenBucket::new(limit));
+
+            // Start over if the limit of the relay changed since the last request.
+            if bucket.limit != limit {
+                *bucket = TokenBucket::new(limit);
+            }
+
+            bucket.reserve()
+        };
+
+        if !wait.is_zero() {
+            tracing::debug!(relay = %relay.name, ?wait, "Rate limited relay request");
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Token bucket state for a single relay.
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    /// Available tokens. Negative when requests are waiting for tokens to be refilled.
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            tokens: limit.burst as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Reserve a token, returning how long the caller must wait before using it.
+    fn reserve(&mut self) -> Duration {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * self.limit.requests_per_second).min(self.limit.burst as f64);
+        self.last_refill = now;
+
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.limit.requests_per_second)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::{RateLimit, RateLimiter};
+    use crate::{Error, Relay};
+
+    fn relay(name: &str) -> Relay {
+        let url = "https://0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae@relay.example.com";
+        Relay::new(name, url).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_burst_then_throttle() {
+        let limiter = RateLimiter::new(None);
+        let relay = relay("limited").with_rate_limit(RateLimit::new(20.0, 2).unwrap());
+
+        // The burst is available immediately.
+        let start = Instant::now();
+        limiter.acquire(&relay).await;
+        limiter.acquire(&relay).await;
+        assert!(start.elapsed() < Duration::from_millis(25));
+
+        // The following requests are spaced by 1 / 20 seconds.
+        for _ in 0..4 {
+            limiter.acquire(&relay).await;
+        }
+        assert!(start.elapsed() >= Duration::from_millis(190));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_unlimited_relay() {
+        let limiter = RateLimiter::new(None);
+        let relay = relay("unlimited");
+
+        let start = Instant::now();
+        for _ in 0..100 {
+            limiter.acquire(&relay).await;
+        }
+        assert!(start.elapsed() < Duration::from_millis(25));
+    }
+
+    #[test]
+    fn test_rate_limit_invalid_rate() {
+        for requests_per_second in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                RateLimit::new(requests_per_second, 1),
+                Err(Error::InvalidRateLimit(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_min_burst() {
+        let limit = RateLimit::new(2.0, 0).unwrap();
+        assert_eq!(limit.burst(), 1);
+        assert_eq!(limit.requests_per_second(), 2.0);
+    }
+}
@@ -0,0 +1,145 @@
+use std::{collections::HashMap, fmt, time::Duration};
+
+use crate::{Error, Result};
+
+/// Outcome of a query to a single relay, as part of a multi-relay query.
+#[derive(Debug)]
+pub struct RelayResponse<T> {
+    /// The data returned by the relay, or the error that occurred.
+    pub result: Result<T>,
+    /// Time it took for the relay to answer, fail or time out.
+    pub latency: Duration,
+}
+
+impl<T> RelayResponse<T> {
+    /// Returns `true` if the relay answered successfully.
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Outcomes of a query performed on multiple relays, keyed by relay name.
+///
+/// Every queried relay has an entry, so "relay returned no data" (a successful, empty response)
+/// can be told apart from "relay was down" (an error).
+#[derive(Debug)]
+pub struct MultiRelayResponse<T> {
+    responses: HashMap<String, RelayResponse<T>>,
+}
+
+impl<T> Default for MultiRelayResponse<T> {
+    fn default() -> Self {
+        Self {
+            responses: HashMap::new(),
+        }
+    }
+}
+
+impl<T> MultiRelayResponse<T> {
+    /// Returns the outcome for the given relay, if it was queried.
+    pub fn get(&self, relay_name: &str) -> Option<&RelayResponse<T>> {
+        self.responses.get(relay_name)
+    }
+
+    /// Returns the number of queried relays.
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    /// Returns `true` if no relays were queried.
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+
+    /// Iterate over the outcomes of all queried relays.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &RelayResponse<T>)> {
+        self.responses
+            .iter()
+            .map(|(name, res)| (name.as_str(), res))
+    }
+
+    /// Iterate over the data returned by the relays that answered successfully.
+    pub fn successes(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.responses
+            .iter()
+            .filter_map(|(name, res)| res.result.as_ref().ok().map(|data| (name.as_str(), data)))
+    }
+
+    /// Iterate over the errors of the relays that failed or timed out.
+    pub fn failures(&self) -> impl Iterator<Item = (&str, &Error)> {
+        self.responses
+            .iter()
+            .filter_map(|(name, res)| res.result.as_ref().err().map(|e| (name.as_str(), e)))
+    }
+
+    /// Consume the response, keeping only the data of the relays that answered successfully.
+    pub fn into_successes(self) -> HashMap<String, T> {
+        self.responses
+            .into_iter()
+            .filter_map(|(name, res)| res.result.ok().map(|data| (name, data)))
+            .collect()
+    }
+
+    /// Returns a summary of the number of successful and failed relays.
+    pub fn summary(&self) -> MultiRelaySummary {
+        let succeeded = self.responses.values().filter(|res| res.is_ok()).count();
+
+        MultiRelaySummary {
+            total: self.responses.len(),
+            succeeded,
+            failed: self.responses.len() - succeeded,
+            max_latency: self
+                .responses
+                .values()
+                .map(|res| res.latency)
+                .max()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl<T> FromIterator<(String, RelayResponse<T>)> for MultiRelayResponse<T> {
+    fn from_iter<I: IntoIterator<Item = (String, RelayResponse<T>)>>(iter: I) -> Self {
+        Self {
+            responses: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<(String, RelayResponse<T>)> for MultiRelayResponse<T> {
+    fn extend<I: IntoIterator<Item = (String, RelayResponse<T>)>>(&mut self, iter: I) {
+        self.responses.extend(iter)
+    }
+}
+
+impl<T> IntoIterator for MultiRelayResponse<T> {
+    type Item = (String, RelayResponse<T>);
+    type IntoIter = std::collections::hash_map::IntoIter<String, RelayResponse<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.responses.into_iter()
+    }
+}
+
+/// Summary of a [`MultiRelayResponse`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MultiRelaySummary {
+    /// Number of queried relays.
+    pub total: usize,
+    /// Number of relays that answered successfully.
+    pub succeeded: usize,
+    /// Number of relays that failed or timed out.
+    pub failed: usize,
+    /// Latency of the slowest relay.
+    pub max_latency: Duration,
+}
+
+impl fmt::Display for MultiRelaySummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} relays succeeded, {} failed (slowest: {:?})",
+            self.succeeded, self.total, self.failed, self.max_latency
+        )
+    }
+}
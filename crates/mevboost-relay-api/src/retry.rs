@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::{header::HeaderMap, header::RETRY_AFTER, StatusCode};
+
+use crate::Error;
+
+/// Policy for retrying failed relay requests with exponential backoff.
+///
+/// A request is retried if the relay responds with one of the `retryable_statuses`,
+/// or if the request times out or fails to connect. If the relay responds with a
+/// `Retry-After` header, its value is used instead of the computed backoff.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one. `1` disables retries.
+    pub max_attempts: u32,
+    /// Backoff before the first retry. Doubles with every following retry.
+    pub base_backoff: Duration,
+    /// Upper bound for the computed backoff.
+    pub max_backoff: Duration,
+    /// Fraction of the backoff that is randomized, between `0.0` (no jitter) and `1.0`.
+    pub jitter: f64,
+    /// Longest `Retry-After` delay the client is willing to wait.
+    /// If a relay asks for a longer delay, the request fails instead.
+    pub max_retry_after: Duration,
+    /// HTTP status codes that are considered transient.
+    pub retryable_statuses: Vec<StatusCode>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(10),
+            jitter: 0.5,
+            max_retry_after: Duration::from_secs(60),
+            retryable_statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Returns `true` if the given error is transient and the request should be retried.
+    pub fn is_retryable(&self, error: &Error) -> bool {
+        match error {
+            Error::Transport(e) => e.is_timeout() || e.is_connect(),
+            _ => error
+                .status()
+                .is_some_and(|status| self.retryable_statuses.contains(&status)),
+        }
+    }
+
+    /// Returns the backoff to wait before the given retry (starting at 1), with jitter applied.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exp = 2u32.saturating_pow(retry.saturating_sub(1));
+        let backoff = self.base_backoff.saturating_mul(exp).min(self.max_backoff);
+
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return backoff;
+        }
+
+        backoff.mul_f64(1.0 - jitter * rand::thread_rng().gen::<f64>())
+    }
+}
+
+/// Parse the `Retry-After` header, either in seconds or as an HTTP date.
+pub(crate) fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = date.signed_duration_since(chrono::Utc::now());
+    Some(delay.to_std().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+
+    use super::{parse_retry_after, RetryPolicy};
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy {
+            jitter: 0.0,
+            base_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+            ..Default::default()
+        };
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(4), Duration::from_millis(500));
+        assert_eq!(policy.backoff(64), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_backoff_jitter_bounds() {
+        let policy = RetryPolicy {
+            jitter: 0.5,
+            base_backoff: Duration::from_millis(100),
+            ..Default::default()
+        };
+
+        for _ in 0..100 {
+            let backoff = policy.backoff(1);
+            assert!(backoff >= Duration::from_millis(50) && backoff <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("3"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(3)));
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
+    }
+}
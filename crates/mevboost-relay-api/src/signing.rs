@@ -0,0 +1,273 @@
+use blst::{
+    min_pk::{PublicKey, Signature},
+    BLST_ERROR,
+};
+
+use crate::{
+    consensus::{SignedBuilderBid, SignedValidatorRegistration},
+    constants,
+    primitives::{BlsPublicKey, BlsSignature, ForkVersion, Hash32},
+    ssz::{hash_concat, merkleize, TreeHash},
+    Error, Result,
+};
+
+/// Returns the domain of the messages signed for the builder API on the network
+/// with the given genesis fork version.
+///
+/// Unlike other domains, the builder domain is computed with an empty genesis validators root,
+/// so that registrations stay valid across forks.
+pub fn compute_builder_domain(genesis_fork_version: ForkVersion) -> Hash32 {
+    let fork_version_root = Hash32::right_padding_from(genesis_fork_version.as_slice());
+    let fork_data_root = merkleize(&[fork_version_root, Hash32::ZERO]);
+
+    let mut domain = Hash32::ZERO;
+    domain[..4].copy_from_slice(&constants::DOMAIN_APPLICATION_BUILDER);
+    domain[4..].copy_from_slice(&fork_data_root[..28]);
+    domain
+}
+
+/// Returns the signing root of a message with the given hash tree root, in the given domain.
+pub fn compute_signing_root(object_root: Hash32, domain: Hash32) -> Hash32 {
+    hash_concat(&object_root, &domain)
+}
+
+/// Verify a BLS signature of the signing root by the given public key.
+///
+/// Returns a description of the failure if the public key or the signature is malformed,
+/// or if the signature does not match.
+pub fn verify_signature(
+    pubkey: &BlsPublicKey,
+    signing_root: &Hash32,
+    signature: &BlsSignature,
+) -> std::result::Result<(), String> {
+    let pubkey = PublicKey::key_validate(pubkey.as_slice())
+        .map_err(|e| format!("invalid public key: {e:?}"))?;
+    let signature = Signature::from_bytes(signature.as_slice())
+        .map_err(|e| format!("invalid signature: {e:?}"))?;
+
+    match signature.verify(
+        true,
+        signing_root.as_slice(),
+        constants::BLS_DST,
+        &[],
+        &pubkey,
+        false,
+    ) {
+        BLST_ERROR::BLST_SUCCESS => Ok(()),
+        e => Err(format!("signature verification failed: {e:?}")),
+    }
+}
+
+impl SignedValidatorRegistration {
+    /// Verify that the registration is signed by its validator, in the builder domain
+    /// of the network with the given genesis fork version.
+    ///
+    /// Fails with [`Error::InvalidRegistration`] if the signature is malformed or does not match.
+    pub fn verify(&self, genesis_fork_version: ForkVersion) -> Result<()> {
+        let invalid = |reason: String| Error::InvalidRegistration {
+            pubkey: self.message.pubkey.to_string(),
+            reason,
+        };
+
+        let message_root = self
+            .message
+            .hash_tree_root()
+            .map_err(|e| invalid(e.to_string()))?;
+        let signing_root =
+            compute_signing_root(message_root, compute_builder_domain(genesis_fork_version));
+
+        verify_signature(&self.message.pubkey, &signing_root, &self.signature).map_err(invalid)
+    }
+}
+
+impl SignedBuilderBid {
+    /// Verify that the bid is signed by the relay with the given public key, in the builder
+    /// domain of the network with the given genesis fork version.
+    ///
+    /// Fails with [`Error::InvalidBidSignature`] if the bid was signed with another key,
+    /// if a list of the bid is longer than its SSZ maximum length, or if the signature
+    /// is malformed or does not match.
+    pub fn verify(
+        &self,
+        relay_pubkey: &BlsPublicKey,
+        genesis_fork_version: ForkVersion,
+    ) -> Result<()> {
+        let invalid = |reason: String| Error::InvalidBidSignature {
+            pubkey: relay_pubkey.to_string(),
+            reason,
+        };
+
+        if self.pubkey() != relay_pubkey {
+            return Err(invalid(format!(
+                "bid signed with another key {}",
+                self.pubkey()
+            )));
+        }
+
+        let message_root = match self {
+            Self::Capella(bid) => bid.message.hash_tree_root(),
+            Self::Deneb(bid) => bid.message.hash_tree_root(),
+            Self::Electra(bid) => bid.message.hash_tree_root(),
+        }
+        .map_err(|e| invalid(e.to_string()))?;
+        let signing_root =
+            compute_signing_root(message_root, compute_builder_domain(genesis_fork_version));
+
+        verify_signature(relay_pubkey, &signing_root, self.signature()).map_err(invalid)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use blst::min_pk::SecretKey;
+
+    use super::{compute_builder_domain, compute_signing_root};
+    use crate::{
+        consensus::{
+            tests::SIGNED_BUILDER_BID_DENEB, BuilderBidElectra, ConsolidationRequest, Signed,
+            SignedBuilderBid, SignedValidatorRegistration, ValidatorRegistration,
+        },
+        constants,
+        primitives::{Address, BlsPublicKey, BlsSignature, ForkVersion, Hash32},
+        ssz::TreeHash,
+        Error,
+    };
+
+    /// Helper function to create a secret key from a seed byte.
+    pub(crate) fn secret_key(seed: u8) -> SecretKey {
+        SecretKey::key_gen(&[seed; 32], &[]).unwrap()
+    }
+
+    /// Helper function to create a registration signed by the validator with the given secret key.
+    pub(crate) fn signed_registration(
+        sk: &SecretKey,
+        genesis_fork_version: ForkVersion,
+    ) -> SignedValidatorRegistration {
+        let message = ValidatorRegistration {
+            fee_recipient: Address::repeat_byte(0x01),
+            gas_limit: 30_000_000,
+            timestamp: 1_700_000_000,
+            pubkey: BlsPublicKey::from(sk.sk_to_pk().to_bytes()),
+        };
+        let signing_root = compute_signing_root(
+            message.hash_tree_root().unwrap(),
+            compute_builder_domain(genesis_fork_version),
+        );
+        let signature = sk.sign(signing_root.as_slice(), constants::BLS_DST, &[]);
+
+        SignedValidatorRegistration {
+            message,
+            signature: BlsSignature::from(signature.to_bytes()),
+        }
+    }
+
+    /// Helper function to sign the Deneb bid fixture, or an Electra bid based on it,
+    /// with the given relay secret key.
+    pub(crate) fn signed_bid(sk: &SecretKey, electra: bool) -> SignedBuilderBid {
+        let mut bid: SignedBuilderBid = serde_json::from_str(SIGNED_BUILDER_BID_DENEB).unwrap();
+        let pubkey = BlsPublicKey::from(sk.sk_to_pk().to_bytes());
+        let domain = compute_builder_domain(constants::MAINNET_GENESIS_FORK_VERSION);
+        let sign = |root: Hash32| {
+            let signing_root = compute_signing_root(root, domain);
+            let signature = sk.sign(signing_root.as_slice(), constants::BLS_DST, &[]);
+            BlsSignature::from(signature.to_bytes())
+        };
+
+        if electra {
+            let SignedBuilderBid::Deneb(deneb) = bid else {
+                unreachable!()
+            };
+            let mut message = BuilderBidElectra {
+                header: deneb.message.header,
+                blob_kzg_commitments: deneb.message.blob_kzg_commitments,
+                execution_requests: Default::default(),
+                value: deneb.message.value,
+                pubkey,
+            };
+            message
+                .execution_requests
+                .consolidations
+                .push(ConsolidationRequest {
+                    source_address: Address::repeat_byte(0x01),
+                    source_pubkey: pubkey,
+                    target_pubkey: pubkey,
+                });
+            let signature = sign(message.hash_tree_root().unwrap());
+            bid = SignedBuilderBid::Electra(Signed { message, signature });
+        } else if let SignedBuilderBid::Deneb(deneb) = &mut bid {
+            deneb.message.pubkey = pubkey;
+            deneb.signature = sign(deneb.message.hash_tree_root().unwrap());
+        }
+        bid
+    }
+
+    #[test]
+    fn test_compute_builder_domain() {
+        let domain: Hash32 = "0x00000001f5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a9"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            compute_builder_domain(constants::MAINNET_GENESIS_FORK_VERSION),
+            domain
+        );
+    }
+
+    #[test]
+    fn test_verify_registration() {
+        let mainnet = constants::MAINNET_GENESIS_FORK_VERSION;
+        let registration = signed_registration(&secret_key(1), mainnet);
+        assert!(registration.verify(mainnet).is_ok());
+
+        // Signed for another network.
+        let other_network = ForkVersion::from([0x01, 0x01, 0x70, 0x00]);
+        assert!(matches!(
+            registration.verify(other_network),
+            Err(Error::InvalidRegistration { .. })
+        ));
+
+        // Tampered message.
+        let mut tampered = registration.clone();
+        tampered.message.gas_limit += 1;
+        assert!(tampered.verify(mainnet).is_err());
+
+        // Signed by another validator.
+        let mut other_signer = registration.clone();
+        other_signer.signature = signed_registration(&secret_key(2), mainnet).signature;
+        assert!(other_signer.verify(mainnet).is_err());
+
+        // Malformed signature.
+        let mut malformed = registration;
+        malformed.signature = BlsSignature::repeat_byte(0xff);
+        assert!(malformed.verify(mainnet).is_err());
+    }
+
+    #[test]
+    fn test_verify_bid() {
+        let mainnet = constants::MAINNET_GENESIS_FORK_VERSION;
+        let relay = secret_key(1);
+        let relay_pubkey = BlsPublicKey::from(relay.sk_to_pk().to_bytes());
+
+        for electra in [false, true] {
+            let bid = signed_bid(&relay, electra);
+            assert!(bid.verify(&relay_pubkey, mainnet).is_ok());
+
+            // Signed by another relay.
+            let other_pubkey = BlsPublicKey::from(secret_key(2).sk_to_pk().to_bytes());
+            assert!(matches!(
+                bid.verify(&other_pubkey, mainnet),
+                Err(Error::InvalidBidSignature { .. })
+            ));
+
+            // Tampered value.
+            let mut tampered = bid.clone();
+            match &mut tampered {
+                SignedBuilderBid::Deneb(bid) => bid.message.value = Default::default(),
+                SignedBuilderBid::Electra(bid) => {
+                    bid.message.execution_requests = Default::default()
+                }
+                SignedBuilderBid::Capella(_) => unreachable!(),
+            }
+            assert!(tampered.verify(&relay_pubkey, mainnet).is_err());
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use std::path::Path;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::{
+    consensus::Fork,
+    constants,
+    primitives::{quoted_u64, ForkVersion},
+    Error, Result, SlotClock,
+};
+
+/// Epoch of forks that are not scheduled.
+pub const FAR_FUTURE_EPOCH: u64 = u64::MAX;
+
+/// Parameters of a consensus chain used by the client, such as the slot duration
+/// and the genesis fork version used to verify signatures.
+///
+/// Built-in networks provide their spec with [`Network::chain_spec()`](crate::Network::chain_spec),
+/// and custom networks can be loaded from a consensus-spec `config.yaml` with [`ChainSpec::from_yaml()`].
+///
+/// The slot duration and number of slots per epoch are only set through
+/// [`ChainSpec::with_slot_timing()`], which rejects zero values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainSpec {
+    /// Name of the network, if known.
+    pub config_name: Option<String>,
+    /// Genesis time of the chain, in seconds since the Unix epoch.
+    pub genesis_time: u64,
+    /// Genesis fork version of the chain, used to compute the builder signing domain.
+    pub genesis_fork_version: ForkVersion,
+    /// Duration of a slot, in seconds.
+    pub(crate) seconds_per_slot: u64,
+    /// Number of slots per epoch.
+    pub(crate) slots_per_epoch: u64,
+    /// Activation epoch of the Capella fork.
+    pub capella_fork_epoch: u64,
+    /// Activation epoch of the Deneb fork.
+    pub deneb_fork_epoch: u64,
+    /// Activation epoch of the Electra fork.
+    pub electra_fork_epoch: u64,
+}
+
+impl Default for ChainSpec {
+    /// Returns the spec of Ethereum mainnet.
+    fn default() -> Self {
+        crate::Network::Mainnet.chain_spec()
+    }
+}
+
+/// Subset of the keys of a consensus-spec `config.yaml` used to build a [`ChainSpec`].
+#[derive(Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+struct Config {
+    config_name: Option<String>,
+    #[serde(deserialize_with = "deserialize_fork_version")]
+    genesis_fork_version: ForkVersion,
+    #[serde(with = "quoted_u64")]
+    seconds_per_slot: u64,
+    /// Part of the preset rather than the config, but included by most devnet configs.
+    #[serde(default = "default_slots_per_epoch", with = "quoted_u64")]
+    slots_per_epoch: u64,
+    #[serde(default = "far_future_epoch", with = "quoted_u64")]
+    capella_fork_epoch: u64,
+    #[serde(default = "far_future_epoch", with = "quoted_u64")]
+    deneb_fork_epoch: u64,
+    #[serde(default = "far_future_epoch", with = "quoted_u64")]
+    electra_fork_epoch: u64,
+}
+
+fn default_slots_per_epoch() -> u64 {
+    constants::SLOTS_PER_EPOCH
+}
+
+fn far_future_epoch() -> u64 {
+    FAR_FUTURE_EPOCH
+}
+
+/// Deserialize a fork version, which YAML parsers may otherwise read as a hex number.
+fn deserialize_fork_version<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<ForkVersion, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    value.parse().map_err(serde::de::Error::custom)
+}
+
+impl ChainSpec {
+    /// Load the spec from the contents of a consensus-spec `config.yaml`, for the chain
+    /// started at the given genesis time, in seconds since the Unix epoch.
+    ///
+    /// The genesis time is not part of the config: `MIN_GENESIS_TIME + GENESIS_DELAY` is only
+    /// a lower bound, so it must be taken from the genesis state or a beacon node.
+    /// `SLOTS_PER_EPOCH` defaults to 32, and forks without an epoch are not scheduled.
+    ///
+    /// Fails with [`Error::InvalidChainSpec`] if a required key is missing or malformed.
+    pub fn from_yaml(yaml: &str, genesis_time: u64) -> Result<Self> {
+        let config: Config =
+            serde_yaml::from_str(yaml).map_err(|e| Error::InvalidChainSpec(e.to_string()))?;
+
+        Self {
+            config_name: config.config_name,
+            genesis_time,
+            genesis_fork_version: config.genesis_fork_version,
+            seconds_per_slot: constants::SECONDS_PER_SLOT,
+            slots_per_epoch: constants::SLOTS_PER_EPOCH,
+            capella_fork_epoch: config.capella_fork_epoch,
+            deneb_fork_epoch: config.deneb_fork_epoch,
+            electra_fork_epoch: config.electra_fork_epoch,
+        }
+        .with_slot_timing(config.seconds_per_slot, config.slots_per_epoch)
+    }
+
+    /// Load the spec from a consensus-spec `config.yaml` file. See [`ChainSpec::from_yaml()`].
+    pub fn from_file(path: impl AsRef<Path>, genesis_time: u64) -> Result<Self> {
+        let path = path.as_ref();
+        let yaml = std::fs::read_to_string(path)
+            .map_err(|e| Error::InvalidChainSpec(format!("{}: {}", path.display(), e)))?;
+        Self::from_yaml(&yaml, genesis_time)
+    }
+
+    /// Set the genesis time of the chain, in seconds since the Unix epoch.
+    pub fn with_genesis_time(mut self, genesis_time: u64) -> Self {
+        self.genesis_time = genesis_time;
+        self
+    }
+
+    /// Set the duration of a slot, in seconds, and the number of slots per epoch.
+    ///
+    /// Fails with [`Error::InvalidChainSpec`] if either value is zero.
+    pub fn with_slot_timing(mut self, seconds_per_slot: u64, slots_per_epoch: u64) -> Result<Self> {
+        if seconds_per_slot == 0 || slots_per_epoch == 0 {
+            return Err(Error::InvalidChainSpec(
+                "SECONDS_PER_SLOT and SLOTS_PER_EPOCH must be positive".to_string(),
+            ));
+        }
+
+        self.seconds_per_slot = seconds_per_slot;
+        self.slots_per_epoch = slots_per_epoch;
+        Ok(self)
+    }
+
+    /// Returns the duration of a slot, in seconds.
+    pub fn seconds_per_slot(&self) -> u64 {
+        self.seconds_per_slot
+    }
+
+    /// Returns the number of slots per epoch.
+    pub fn slots_per_epoch(&self) -> u64 {
+        self.slots_per_epoch
+    }
+
+    /// Returns a clock mapping wall-clock time to the slots of the chain.
+    pub fn slot_clock(&self) -> SlotClock {
+        SlotClock::new(self)
+    }
+
+    /// Returns the epoch of the given slot.
+    pub fn epoch_of_slot(&self, slot: u64) -> u64 {
+        slot / self.slots_per_epoch
+    }
+
+    /// Returns the first slot of the given epoch.
+    pub fn start_slot_of_epoch(&self, epoch: u64) -> u64 {
+        epoch.saturating_mul(self.slots_per_epoch)
+    }
+
+    /// Returns the fork active at the given epoch, or `None` before the Capella fork.
+    pub fn fork_at_epoch(&self, epoch: u64) -> Option<Fork> {
+        if epoch >= self.electra_fork_epoch {
+            Some(Fork::Electra)
+        } else if epoch >= self.deneb_fork_epoch {
+            Some(Fork::Deneb)
+        } else if epoch >= self.capella_fork_epoch {
+            Some(Fork::Capella)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the fork active at the given slot, or `None` before the Capella fork.
+    pub fn fork_at_slot(&self, slot: u64) -> Option<Fork> {
+        self.fork_at_epoch(self.epoch_of_slot(slot))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChainSpec, FAR_FUTURE_EPOCH};
+    use crate::{consensus::Fork, primitives::ForkVersion, Error, Network};
+
+    /// Excerpt of the Hoodi `config.yaml`, with `SLOTS_PER_EPOCH` added as in devnet configs.
+    const HOODI_CONFIG: &str = r#"
+# Extends the mainnet preset
+PRESET_BASE: 'mainnet'
+CONFIG_NAME: 'hoodi'
+
+# Genesis
+MIN_GENESIS_ACTIVE_VALIDATOR_COUNT: 16384
+MIN_GENESIS_TIME: 1742212800
+GENESIS_FORK_VERSION: 0x10000910
+GENESIS_DELAY: 600
+
+# Forking
+ALTAIR_FORK_VERSION: 0x20000910
+ALTAIR_FORK_EPOCH: 0
+CAPELLA_FORK_VERSION: 0x40000910
+CAPELLA_FORK_EPOCH: 0
+DENEB_FORK_VERSION: 0x50000910
+DENEB_FORK_EPOCH: 0
+ELECTRA_FORK_VERSION: 0x60000910
+ELECTRA_FORK_EPOCH: 2048
+FULU_FORK_EPOCH: 18446744073709551615
+
+# Time parameters
+SECONDS_PER_SLOT: 12
+SLOTS_PER_EPOCH: 32
+"#;
+
+    #[test]
+    fn test_chain_spec_from_yaml() {
+        let spec = ChainSpec::from_yaml(HOODI_CONFIG, 1_742_213_400).unwrap();
+
+        assert_eq!(spec, Network::Hoodi.chain_spec());
+        assert_eq!(spec.config_name.as_deref(), Some("hoodi"));
+        assert_eq!(spec.fork_at_slot(2048 * 32 - 1), Some(Fork::Deneb));
+        assert_eq!(spec.fork_at_slot(2048 * 32), Some(Fork::Electra));
+    }
+
+    #[test]
+    fn test_chain_spec_from_yaml_devnet() {
+        let yaml = r#"
+MIN_GENESIS_TIME: "1700000000"
+GENESIS_FORK_VERSION: "0x10000038"
+SECONDS_PER_SLOT: 6
+SLOTS_PER_EPOCH: 8
+DENEB_FORK_EPOCH: 1
+"#;
+        let spec = ChainSpec::from_yaml(yaml, 1_700_000_023).unwrap();
+
+        assert_eq!(spec.genesis_time, 1_700_000_023);
+        assert_eq!(spec.seconds_per_slot(), 6);
+        assert_eq!(spec.slots_per_epoch(), 8);
+        assert_eq!(
+            spec.genesis_fork_version,
+            ForkVersion::from([0x10, 0x00, 0x00, 0x38])
+        );
+        assert_eq!(spec.epoch_of_slot(17), 2);
+        assert_eq!(spec.start_slot_of_epoch(2), 16);
+        assert_eq!(spec.capella_fork_epoch, FAR_FUTURE_EPOCH);
+        assert_eq!(spec.fork_at_epoch(0), None);
+        assert_eq!(spec.fork_at_epoch(1), Some(Fork::Deneb));
+
+        for yaml in [
+            "SECONDS_PER_SLOT: 12",
+            &yaml.replace("SECONDS_PER_SLOT: 6", "SECONDS_PER_SLOT: 0"),
+        ] {
+            assert!(matches!(
+                ChainSpec::from_yaml(yaml, 1_700_000_023),
+                Err(Error::InvalidChainSpec(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_chain_spec_slot_timing() {
+        let spec = ChainSpec::default().with_slot_timing(6, 8).unwrap();
+        assert_eq!(spec.seconds_per_slot(), 6);
+        assert_eq!(spec.epoch_of_slot(17), 2);
+
+        for (seconds_per_slot, slots_per_epoch) in [(0, 32), (12, 0)] {
+            assert!(matches!(
+                ChainSpec::default().with_slot_timing(seconds_per_slot, slots_per_epoch),
+                Err(Error::InvalidChainSpec(_))
+            ));
+        }
+    }
+}
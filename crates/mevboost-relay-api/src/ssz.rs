@@ -0,0 +1,839 @@
+use alloy_primitives::FixedBytes;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    consensus::{
+        BlobsBundle, BuilderBidCapella, BuilderBidDeneb, BuilderBidElectra, ConsolidationRequest,
+        DepositRequest, ExecutionPayloadCapella, ExecutionPayloadDeneb,
+        ExecutionPayloadHeaderCapella, ExecutionPayloadHeaderDeneb, ExecutionRequests, Signed,
+        ValidatorRegistration, Withdrawal, WithdrawalRequest,
+    },
+    primitives::{Address, Bloom, BlsPublicKey, BlsSignature, Bytes, Hash32, Wei, U256},
+    types::{
+        BidTrace, SubmitBlockRequest, SubmitBlockRequestCapella, SubmitBlockRequestDeneb,
+        SubmitBlockRequestElectra,
+    },
+    Error, Result,
+};
+
+/// Number of bytes of the offset of a variable-size field.
+pub(crate) const BYTES_PER_LENGTH_OFFSET: usize = 4;
+
+/// Maximum length of the extra data of an execution payload.
+pub(crate) const MAX_EXTRA_DATA_BYTES: usize = 32;
+
+/// Length of a blob.
+pub(crate) const BYTES_PER_BLOB: usize = 131_072;
+
+/// Maximum number of blob KZG commitments in a block.
+pub(crate) const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize = 4096;
+
+/// Maximum number of deposit requests in an execution payload.
+pub(crate) const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize = 8192;
+
+/// Maximum number of withdrawal requests in an execution payload.
+pub(crate) const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize = 16;
+
+/// Maximum number of consolidation requests in an execution payload.
+pub(crate) const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize = 2;
+
+/// A type that can be SSZ-encoded.
+pub trait Encode {
+    /// Returns `true` if all values of the type have the same encoded length.
+    fn is_ssz_fixed_len() -> bool;
+
+    /// Returns the length of the type in the fixed part of a container,
+    /// which is the size of an offset for variable-size types.
+    fn ssz_fixed_len() -> usize {
+        BYTES_PER_LENGTH_OFFSET
+    }
+
+    /// Append the encoded value to the buffer.
+    fn ssz_append(&self, buf: &mut Vec<u8>);
+
+    /// Returns the encoded value.
+    fn as_ssz_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.ssz_append(&mut buf);
+        buf
+    }
+}
+
+/// Encoder of a container, which writes the fixed-size fields and the offsets of the
+/// variable-size fields first, followed by the variable-size fields.
+#[derive(Debug)]
+pub(crate) struct ContainerEncoder<'a> {
+    buf: &'a mut Vec<u8>,
+    /// Length of the fixed part of the container.
+    fixed_len: usize,
+    /// Encoded variable-size fields.
+    variable: Vec<u8>,
+}
+
+impl<'a> ContainerEncoder<'a> {
+    /// Create an encoder appending to the buffer a container with a fixed part of `fixed_len` bytes.
+    pub(crate) fn new(buf: &'a mut Vec<u8>, fixed_len: usize) -> Self {
+        buf.reserve(fixed_len);
+        Self {
+            buf,
+            fixed_len,
+            variable: Vec::new(),
+        }
+    }
+
+    /// Append the next field of the container.
+    pub(crate) fn append<T: Encode>(&mut self, field: &T) {
+        if T::is_ssz_fixed_len() {
+            field.ssz_append(self.buf);
+        } else {
+            let offset = (self.fixed_len + self.variable.len()) as u32;
+            self.buf.extend_from_slice(&offset.to_le_bytes());
+            field.ssz_append(&mut self.variable);
+        }
+    }
+
+    /// Append the variable-size fields, completing the container.
+    pub(crate) fn finalize(self) {
+        self.buf.extend_from_slice(&self.variable);
+    }
+}
+
+impl Encode for u64 {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        8
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.to_le_bytes());
+    }
+}
+
+impl Encode for U256 {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        32
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.to_le_bytes::<32>());
+    }
+}
+
+impl Encode for Wei {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        U256::ssz_fixed_len()
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        self.0.ssz_append(buf);
+    }
+}
+
+impl<const N: usize> Encode for FixedBytes<N> {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        N
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.as_slice());
+    }
+}
+
+impl Encode for Address {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        20
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.as_slice());
+    }
+}
+
+impl Encode for Bloom {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        256
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.as_slice());
+    }
+}
+
+/// Encoded as a variable-size list of bytes.
+impl Encode for Bytes {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self);
+    }
+}
+
+/// Encoded as a variable-size list.
+impl<T: Encode> Encode for Vec<T> {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        if T::is_ssz_fixed_len() {
+            for item in self {
+                item.ssz_append(buf);
+            }
+        } else {
+            let mut encoder = ContainerEncoder::new(buf, self.len() * BYTES_PER_LENGTH_OFFSET);
+            for item in self {
+                encoder.append(item);
+            }
+            encoder.finalize();
+        }
+    }
+}
+
+/// Implement [`Encode`] for a container with the given fields, in order.
+macro_rules! impl_container {
+    ($type:ty { $($field:ident: $field_type:ty),* $(,)? }) => {
+        impl Encode for $type {
+            fn is_ssz_fixed_len() -> bool {
+                $(<$field_type as Encode>::is_ssz_fixed_len())&&*
+            }
+
+            fn ssz_fixed_len() -> usize {
+                if Self::is_ssz_fixed_len() {
+                    0 $(+ <$field_type as Encode>::ssz_fixed_len())*
+                } else {
+                    BYTES_PER_LENGTH_OFFSET
+                }
+            }
+
+            fn ssz_append(&self, buf: &mut Vec<u8>) {
+                let fixed_len = 0 $(+ <$field_type as Encode>::ssz_fixed_len())*;
+                let mut encoder = ContainerEncoder::new(buf, fixed_len);
+                $(encoder.append(&self.$field);)*
+                encoder.finalize();
+            }
+        }
+    };
+}
+
+impl_container!(BidTrace {
+    slot: u64,
+    parent_hash: Hash32,
+    block_hash: Hash32,
+    builder_pubkey: BlsPublicKey,
+    proposer_pubkey: BlsPublicKey,
+    proposer_fee_recipient: Address,
+    gas_limit: u64,
+    gas_used: u64,
+    value: Wei,
+});
+
+impl_container!(Withdrawal {
+    index: u64,
+    validator_index: u64,
+    address: Address,
+    amount: u64,
+});
+
+impl_container!(ExecutionPayloadCapella {
+    parent_hash: Hash32,
+    fee_recipient: Address,
+    state_root: Hash32,
+    receipts_root: Hash32,
+    logs_bloom: Bloom,
+    prev_randao: Hash32,
+    block_number: u64,
+    gas_limit: u64,
+    gas_used: u64,
+    timestamp: u64,
+    extra_data: Bytes,
+    base_fee_per_gas: U256,
+    block_hash: Hash32,
+    transactions: Vec<Bytes>,
+    withdrawals: Vec<Withdrawal>,
+});
+
+impl_container!(ExecutionPayloadDeneb {
+    parent_hash: Hash32,
+    fee_recipient: Address,
+    state_root: Hash32,
+    receipts_root: Hash32,
+    logs_bloom: Bloom,
+    prev_randao: Hash32,
+    block_number: u64,
+    gas_limit: u64,
+    gas_used: u64,
+    timestamp: u64,
+    extra_data: Bytes,
+    base_fee_per_gas: U256,
+    block_hash: Hash32,
+    transactions: Vec<Bytes>,
+    withdrawals: Vec<Withdrawal>,
+    blob_gas_used: u64,
+    excess_blob_gas: u64,
+});
+
+/// Blobs have a fixed size, so unlike other lists of [`Bytes`] they are encoded without offsets.
+/// Bundles are checked with [`BlobsBundle::validate()`] before being encoded.
+impl Encode for BlobsBundle {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        let mut encoder = ContainerEncoder::new(buf, 3 * BYTES_PER_LENGTH_OFFSET);
+        encoder.append(&self.commitments);
+        encoder.append(&self.proofs);
+        encoder.append(&Bytes::from(self.blobs.concat()));
+        encoder.finalize();
+    }
+}
+
+impl_container!(DepositRequest {
+    pubkey: BlsPublicKey,
+    withdrawal_credentials: Hash32,
+    amount: u64,
+    signature: BlsSignature,
+    index: u64,
+});
+
+impl_container!(WithdrawalRequest {
+    source_address: Address,
+    validator_pubkey: BlsPublicKey,
+    amount: u64,
+});
+
+impl_container!(ConsolidationRequest {
+    source_address: Address,
+    source_pubkey: BlsPublicKey,
+    target_pubkey: BlsPublicKey,
+});
+
+impl_container!(ExecutionRequests {
+    deposits: Vec<DepositRequest>,
+    withdrawals: Vec<WithdrawalRequest>,
+    consolidations: Vec<ConsolidationRequest>,
+});
+
+impl_container!(SubmitBlockRequestCapella {
+    message: BidTrace,
+    execution_payload: ExecutionPayloadCapella,
+    signature: BlsSignature,
+});
+
+impl_container!(SubmitBlockRequestDeneb {
+    message: BidTrace,
+    execution_payload: ExecutionPayloadDeneb,
+    blobs_bundle: BlobsBundle,
+    signature: BlsSignature,
+});
+
+impl_container!(SubmitBlockRequestElectra {
+    message: BidTrace,
+    execution_payload: ExecutionPayloadDeneb,
+    blobs_bundle: BlobsBundle,
+    execution_requests: ExecutionRequests,
+    signature: BlsSignature,
+});
+
+impl_container!(ValidatorRegistration {
+    fee_recipient: Address,
+    gas_limit: u64,
+    timestamp: u64,
+    pubkey: BlsPublicKey,
+});
+
+impl<T: Encode> Encode for Signed<T> {
+    fn is_ssz_fixed_len() -> bool {
+        T::is_ssz_fixed_len()
+    }
+
+    fn ssz_fixed_len() -> usize {
+        if T::is_ssz_fixed_len() {
+            T::ssz_fixed_len() + BlsSignature::ssz_fixed_len()
+        } else {
+            BYTES_PER_LENGTH_OFFSET
+        }
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        let fixed_len = T::ssz_fixed_len() + BlsSignature::ssz_fixed_len();
+        let mut encoder = ContainerEncoder::new(buf, fixed_len);
+        encoder.append(&self.message);
+        encoder.append(&self.signature);
+        encoder.finalize();
+    }
+}
+
+/// Encoded as the request of its fork, which is sent separately.
+impl Encode for SubmitBlockRequest {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::Capella(request) => request.ssz_append(buf),
+            Self::Deneb(request) => request.ssz_append(buf),
+            Self::Electra(request) => request.ssz_append(buf),
+        }
+    }
+}
+
+/// A type with an SSZ hash tree root, which is the message signed by validators and builders.
+pub trait TreeHash {
+    /// Returns the hash tree root of the value.
+    ///
+    /// Fails with [`Error::SszListTooLong`] if a list of the value is longer than its maximum length.
+    fn hash_tree_root(&self) -> Result<Hash32>;
+}
+
+/// Returns the root of the Merkle tree of the given chunks, padded with zero chunks
+/// to the next power of two.
+pub(crate) fn merkleize(chunks: &[Hash32]) -> Hash32 {
+    merkleize_with_limit(chunks, chunks.len())
+}
+
+/// Returns the root of the Merkle tree of the given chunks, padded with zero chunks
+/// to the next power of two of `limit`, as done for lists.
+///
+/// # Panics
+///
+/// Panics if there are more chunks than the limit, which callers check beforehand.
+pub(crate) fn merkleize_with_limit(chunks: &[Hash32], limit: usize) -> Hash32 {
+    assert!(chunks.len() <= limit, "too many chunks for the list limit");
+
+    let depth = limit.next_power_of_two().trailing_zeros();
+    let mut layer = chunks.to_vec();
+    // Root of a subtree of zero chunks at the current depth.
+    let mut zero = Hash32::ZERO;
+
+    for _ in 0..depth {
+        if layer.len() % 2 == 1 {
+            layer.push(zero);
+        }
+        layer = layer
+            .chunks(2)
+            .map(|pair| hash_concat(&pair[0], &pair[1]))
+            .collect();
+        zero = hash_concat(&zero, &zero);
+    }
+    layer.first().copied().unwrap_or(zero)
+}
+
+/// Returns the root of a list, from the root of its items and its length.
+pub(crate) fn mix_in_length(root: Hash32, len: usize) -> Hash32 {
+    let mut length = Hash32::ZERO;
+    length[..8].copy_from_slice(&(len as u64).to_le_bytes());
+    hash_concat(&root, &length)
+}
+
+/// Fails with [`Error::SszListTooLong`] if a list of `len` items is longer than `max_len`.
+fn check_list_len(len: usize, max_len: usize) -> Result<()> {
+    if len > max_len {
+        return Err(Error::SszListTooLong { len, max_len });
+    }
+    Ok(())
+}
+
+/// Returns the root of a list of bytes with the given maximum length.
+fn byte_list_root(bytes: &[u8], max_len: usize) -> Result<Hash32> {
+    check_list_len(bytes.len(), max_len)?;
+    Ok(mix_in_length(
+        merkleize_with_limit(&pack(bytes), max_len.div_ceil(32)),
+        bytes.len(),
+    ))
+}
+
+/// Returns the root of a list of composite items with the given maximum length.
+fn list_root<T: TreeHash>(items: &[T], max_len: usize) -> Result<Hash32> {
+    check_list_len(items.len(), max_len)?;
+    let roots = items
+        .iter()
+        .map(TreeHash::hash_tree_root)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(mix_in_length(
+        merkleize_with_limit(&roots, max_len),
+        items.len(),
+    ))
+}
+
+/// Returns the SHA-256 hash of the concatenation of two chunks.
+pub(crate) fn hash_concat(left: &Hash32, right: &Hash32) -> Hash32 {
+    Hash32::from_slice(
+        &Sha256::new()
+            .chain_update(left)
+            .chain_update(right)
+            .finalize(),
+    )
+}
+
+/// Returns the chunks of the given bytes, with the last chunk padded with zeros.
+fn pack(bytes: &[u8]) -> Vec<Hash32> {
+    bytes
+        .chunks(32)
+        .map(|chunk| {
+            let mut padded = Hash32::ZERO;
+            padded[..chunk.len()].copy_from_slice(chunk);
+            padded
+        })
+        .collect()
+}
+
+impl TreeHash for u64 {
+    fn hash_tree_root(&self) -> Result<Hash32> {
+        Ok(merkleize(&pack(&self.to_le_bytes())))
+    }
+}
+
+impl TreeHash for U256 {
+    fn hash_tree_root(&self) -> Result<Hash32> {
+        Ok(Hash32::from(self.to_le_bytes::<32>()))
+    }
+}
+
+impl TreeHash for Wei {
+    fn hash_tree_root(&self) -> Result<Hash32> {
+        self.0.hash_tree_root()
+    }
+}
+
+impl<const N: usize> TreeHash for FixedBytes<N> {
+    fn hash_tree_root(&self) -> Result<Hash32> {
+        Ok(merkleize(&pack(self.as_slice())))
+    }
+}
+
+impl TreeHash for Address {
+    fn hash_tree_root(&self) -> Result<Hash32> {
+        Ok(merkleize(&pack(self.as_slice())))
+    }
+}
+
+impl TreeHash for Bloom {
+    fn hash_tree_root(&self) -> Result<Hash32> {
+        Ok(merkleize(&pack(self.as_slice())))
+    }
+}
+
+/// Implement [`TreeHash`] for a container with the given fields, in order.
+macro_rules! impl_tree_hash {
+    ($type:ty { $($field:ident),* $(,)? }) => {
+        impl TreeHash for $type {
+            fn hash_tree_root(&self) -> Result<Hash32> {
+                Ok(merkleize(&[$(self.$field.hash_tree_root()?),*]))
+            }
+        }
+    };
+}
+
+impl_tree_hash!(ValidatorRegistration {
+    fee_recipient,
+    gas_limit,
+    timestamp,
+    pubkey,
+});
+
+impl_tree_hash!(BidTrace {
+    slot,
+    parent_hash,
+    block_hash,
+    builder_pubkey,
+    proposer_pubkey,
+    proposer_fee_recipient,
+    gas_limit,
+    gas_used,
+    value,
+});
+
+impl TreeHash for ExecutionPayloadHeaderCapella {
+    fn hash_tree_root(&self) -> Result<Hash32> {
+        Ok(merkleize(&[
+            self.parent_hash.hash_tree_root()?,
+            self.fee_recipient.hash_tree_root()?,
+            self.state_root.hash_tree_root()?,
+            self.receipts_root.hash_tree_root()?,
+            self.logs_bloom.hash_tree_root()?,
+            self.prev_randao.hash_tree_root()?,
+            self.block_number.hash_tree_root()?,
+            self.gas_limit.hash_tree_root()?,
+            self.gas_used.hash_tree_root()?,
+            self.timestamp.hash_tree_root()?,
+            byte_list_root(&self.extra_data, MAX_EXTRA_DATA_BYTES)?,
+            self.base_fee_per_gas.hash_tree_root()?,
+            self.block_hash.hash_tree_root()?,
+            self.transactions_root.hash_tree_root()?,
+            self.withdrawals_root.hash_tree_root()?,
+        ]))
+    }
+}
+
+impl TreeHash for ExecutionPayloadHeaderDeneb {
+    fn hash_tree_root(&self) -> Result<Hash32> {
+        Ok(merkleize(&[
+            self.parent_hash.hash_tree_root()?,
+            self.fee_recipient.hash_tree_root()?,
+            self.state_root.hash_tree_root()?,
+            self.receipts_root.hash_tree_root()?,
+            self.logs_bloom.hash_tree_root()?,
+            self.prev_randao.hash_tree_root()?,
+            self.block_number.hash_tree_root()?,
+            self.gas_limit.hash_tree_root()?,
+            self.gas_used.hash_tree_root()?,
+            self.timestamp.hash_tree_root()?,
+            byte_list_root(&self.extra_data, MAX_EXTRA_DATA_BYTES)?,
+            self.base_fee_per_gas.hash_tree_root()?,
+            self.block_hash.hash_tree_root()?,
+            self.transactions_root.hash_tree_root()?,
+            self.withdrawals_root.hash_tree_root()?,
+            self.blob_gas_used.hash_tree_root()?,
+            self.excess_blob_gas.hash_tree_root()?,
+        ]))
+    }
+}
+
+impl_tree_hash!(DepositRequest {
+    pubkey,
+    withdrawal_credentials,
+    amount,
+    signature,
+    index,
+});
+
+impl_tree_hash!(WithdrawalRequest {
+    source_address,
+    validator_pubkey,
+    amount,
+});
+
+impl_tree_hash!(ConsolidationRequest {
+    source_address,
+    source_pubkey,
+    target_pubkey,
+});
+
+impl TreeHash for ExecutionRequests {
+    fn hash_tree_root(&self) -> Result<Hash32> {
+        Ok(merkleize(&[
+            list_root(&self.deposits, MAX_DEPOSIT_REQUESTS_PER_PAYLOAD)?,
+            list_root(&self.withdrawals, MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD)?,
+            list_root(&self.consolidations, MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD)?,
+        ]))
+    }
+}
+
+impl_tree_hash!(BuilderBidCapella {
+    header,
+    value,
+    pubkey,
+});
+
+impl TreeHash for BuilderBidDeneb {
+    fn hash_tree_root(&self) -> Result<Hash32> {
+        Ok(merkleize(&[
+            self.header.hash_tree_root()?,
+            list_root(&self.blob_kzg_commitments, MAX_BLOB_COMMITMENTS_PER_BLOCK)?,
+            self.value.hash_tree_root()?,
+            self.pubkey.hash_tree_root()?,
+        ]))
+    }
+}
+
+impl TreeHash for BuilderBidElectra {
+    fn hash_tree_root(&self) -> Result<Hash32> {
+        Ok(merkleize(&[
+            self.header.hash_tree_root()?,
+            list_root(&self.blob_kzg_commitments, MAX_BLOB_COMMITMENTS_PER_BLOCK)?,
+            self.execution_requests.hash_tree_root()?,
+            self.value.hash_tree_root()?,
+            self.pubkey.hash_tree_root()?,
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::{Digest, Sha256};
+
+    use super::{hash_concat, merkleize, merkleize_with_limit, Encode, TreeHash};
+    use crate::{
+        consensus::{
+            ConsolidationRequest, ExecutionPayloadCapella, ExecutionRequests,
+            SignedValidatorRegistration, ValidatorRegistration, Withdrawal,
+        },
+        primitives::{Address, BlsPublicKey, BlsSignature, Bytes, Hash32, Wei},
+        types::{BidTrace, SubmitBlockRequestCapella},
+        Error,
+    };
+
+    #[test]
+    fn test_encode_bid_trace() {
+        let bid_trace = BidTrace {
+            slot: 1,
+            parent_hash: Hash32::repeat_byte(0x11),
+            value: Wei::from(2u64),
+            ..Default::default()
+        };
+        let bytes = bid_trace.as_ssz_bytes();
+
+        assert!(BidTrace::is_ssz_fixed_len());
+        assert_eq!(BidTrace::ssz_fixed_len(), 236);
+        assert_eq!(bytes.len(), 236);
+        assert_eq!(bytes[..8], 1u64.to_le_bytes());
+        assert_eq!(bytes[8..40], [0x11; 32]);
+        assert_eq!(bytes[204], 2);
+        assert!(bytes[205..].iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn test_encode_variable_fields() {
+        let request = SubmitBlockRequestCapella {
+            execution_payload: ExecutionPayloadCapella {
+                extra_data: Bytes::from_static(b"extra"),
+                transactions: vec![Bytes::from_static(&[1, 2]), Bytes::from_static(&[3])],
+                withdrawals: vec![Withdrawal {
+                    address: Address::repeat_byte(0xaa),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let bytes = request.as_ssz_bytes();
+        let offset = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap()) as usize;
+
+        // Bid trace, offset of the payload, signature.
+        assert_eq!(offset(236), 236 + 4 + 96);
+        let payload = &bytes[offset(236)..];
+
+        // The fixed part of the payload is 512 bytes long. Its variable part holds the extra data,
+        // then the offsets of the two transactions followed by the transactions, then the withdrawal.
+        let payload_offset =
+            |at: usize| u32::from_le_bytes(payload[at..at + 4].try_into().unwrap()) as usize;
+        assert_eq!(payload_offset(436), 512);
+        assert_eq!(&payload[512..517], b"extra");
+        assert_eq!(payload_offset(504), 517);
+        assert_eq!(payload[517..528], [8, 0, 0, 0, 10, 0, 0, 0, 1, 2, 3]);
+        assert_eq!(payload_offset(508), 528);
+        assert_eq!(payload[544..564], [0xaa; 20]);
+        assert_eq!(payload.len(), 528 + 44);
+    }
+
+    fn registration() -> ValidatorRegistration {
+        ValidatorRegistration {
+            fee_recipient: Address::repeat_byte(0x01),
+            gas_limit: 30_000_000,
+            timestamp: 1_700_000_000,
+            pubkey: BlsPublicKey::repeat_byte(0x02),
+        }
+    }
+
+    #[test]
+    fn test_encode_signed_validator_registration() {
+        let registration = SignedValidatorRegistration {
+            message: registration(),
+            signature: BlsSignature::repeat_byte(0x03),
+        };
+        let bytes = registration.as_ssz_bytes();
+
+        assert_eq!(SignedValidatorRegistration::ssz_fixed_len(), 180);
+        assert_eq!(bytes.len(), 180);
+        assert_eq!(bytes[..20], [0x01; 20]);
+        assert_eq!(bytes[20..28], 30_000_000u64.to_le_bytes());
+        assert_eq!(bytes[28..36], 1_700_000_000u64.to_le_bytes());
+        assert_eq!(bytes[36..84], [0x02; 48]);
+        assert_eq!(bytes[84..], [0x03; 96]);
+    }
+
+    #[test]
+    fn test_validator_registration_hash_tree_root() {
+        let sha256 = |left: &[u8], right: &[u8]| -> [u8; 32] {
+            Sha256::new()
+                .chain_update(left)
+                .chain_update(right)
+                .finalize()
+                .into()
+        };
+        let chunk = |bytes: &[u8]| -> [u8; 32] {
+            let mut chunk = [0; 32];
+            chunk[..bytes.len()].copy_from_slice(bytes);
+            chunk
+        };
+
+        let fee_recipient = chunk(&[0x01; 20]);
+        let gas_limit = chunk(&30_000_000u64.to_le_bytes());
+        let timestamp = chunk(&1_700_000_000u64.to_le_bytes());
+        let pubkey = sha256(&[0x02; 32], &chunk(&[0x02; 16]));
+        let expected = sha256(
+            &sha256(&fee_recipient, &gas_limit),
+            &sha256(&timestamp, &pubkey),
+        );
+
+        assert_eq!(
+            registration().hash_tree_root().unwrap(),
+            Hash32::from(expected)
+        );
+    }
+
+    #[test]
+    fn test_merkleize_with_limit() {
+        let chunk = Hash32::repeat_byte(0x01);
+        let zero = Hash32::ZERO;
+        let zero_1 = hash_concat(&zero, &zero);
+
+        assert_eq!(merkleize_with_limit(&[], 4), hash_concat(&zero_1, &zero_1));
+        assert_eq!(merkleize_with_limit(&[chunk], 1), chunk);
+        assert_eq!(
+            merkleize_with_limit(&[chunk], 3),
+            hash_concat(&hash_concat(&chunk, &zero), &zero_1)
+        );
+        assert_eq!(
+            merkleize_with_limit(&[chunk, chunk, chunk], 4),
+            merkleize(&[chunk, chunk, chunk, zero])
+        );
+    }
+
+    #[test]
+    fn test_hash_tree_root_list_too_long() {
+        let requests = ExecutionRequests {
+            consolidations: vec![
+                ConsolidationRequest {
+                    source_address: Address::ZERO,
+                    source_pubkey: BlsPublicKey::ZERO,
+                    target_pubkey: BlsPublicKey::ZERO,
+                };
+                3
+            ],
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            requests.hash_tree_root(),
+            Err(Error::SszListTooLong { len: 3, max_len: 2 })
+        ));
+    }
+}
@@ -1,108 +1,156 @@
-use chrono::prelude::*;
+use std::{fmt, str::FromStr};
+
 use serde::{Deserialize, Serialize};
 use serde_aux::prelude::*;
 
+use crate::{
+    consensus::{
+        BlobsBundle, ExecutionPayloadCapella, ExecutionPayloadDeneb, ExecutionRequests, Fork,
+        SignedValidatorRegistration, ValidatorRegistration,
+    },
+    primitives::{quoted_u64, Address, BlsPublicKey, BlsSignature, Hash32, Wei},
+    ChainSpec, Error, Result,
+};
+
+/// Error object returned by relays on failed requests.
+///
+/// [Visit the docs](https://flashbots.github.io/relay-specs/) for more info.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct RelayErrorResponse {
+    /// The error code, usually matching the HTTP status code.
+    pub code: u16,
+    /// A human readable error message.
+    pub message: String,
+}
+
 /// Validator info for a given slot.
 #[derive(Deserialize, Debug)]
 #[allow(missing_docs)]
 pub struct RegisteredValidator {
     #[serde(deserialize_with = "deserialize_number_from_string")]
     pub slot: u64,
-    pub validator_index: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
+    pub validator_index: Option<u64>,
     pub entry: ValidatorEntry,
 }
 
 /// Validator entry for registered validators in a slot.
-#[derive(Deserialize, Debug)]
-#[allow(missing_docs)]
-pub struct ValidatorEntry {
-    pub message: EntryMessage,
-    pub signature: String,
-}
+pub type ValidatorEntry = SignedValidatorRegistration;
 
 /// Entry message of registered validators in a slot.
-#[derive(Deserialize, Debug)]
-#[allow(missing_docs)]
-pub struct EntryMessage {
-    pub fee_recipient: String,
-    #[serde(deserialize_with = "deserialize_number_from_string")]
-    pub gas_limit: u64,
-    #[serde(deserialize_with = "deserialize_datetime_utc_from_seconds")]
-    pub timestamp: DateTime<Utc>,
-    pub pubkey: String,
-}
+pub type EntryMessage = ValidatorRegistration;
 
 /// Filter arguments for the getPayload bidtraces relay query
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Serialize)]
 pub struct PayloadDeliveredQueryOptions {
-    /// A specific slot number.
+    /// A specific slot number. Cannot be combined with `cursor`.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub slot: Option<u64>,
-    /// A starting slot for multiple results.
+    /// A starting slot for multiple results. Cannot be combined with `slot`.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cursor: Option<u64>,
     /// The number of results.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<u64>,
     /// A block hash.
-    pub block_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<Hash32>,
     /// A specific block number.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub block_number: Option<u64>,
     /// A specific proposer public key.
-    pub proposer_pubkey: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proposer_pubkey: Option<BlsPublicKey>,
     /// A specific builder public key.
-    pub builder_pubkey: Option<String>,
-    /// Sort results in order of: `value` or `-value`.
-    pub order_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_pubkey: Option<BlsPublicKey>,
+    /// Sort results by value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_by: Option<OrderBy>,
 }
 
-impl ToString for PayloadDeliveredQueryOptions {
-    fn to_string(&self) -> String {
-        let mut query = String::new();
-        query.push('?');
-
-        if let Some(slot) = self.slot {
-            query.push_str(&format!("slot={}&", slot));
-        }
-        if let Some(cursor) = self.cursor {
-            query.push_str(&format!("cursor={}&", cursor));
-        }
-        if let Some(limit) = self.limit {
-            query.push_str(&format!("limit={}&", limit));
-        }
-        if let Some(block_hash) = &self.block_hash {
-            query.push_str(&format!("block_hash={}&", block_hash));
-        }
-        if let Some(block_number) = self.block_number {
-            query.push_str(&format!("block_number={}&", block_number));
-        }
-        if let Some(proposer_pubkey) = &self.proposer_pubkey {
-            query.push_str(&format!("proposer_pubkey={}&", proposer_pubkey));
+impl PayloadDeliveredQueryOptions {
+    /// Check that the options form a valid query.
+    ///
+    /// Returns [`Error::InvalidQuery`] if both `slot` and `cursor` are set.
+    pub fn validate(&self) -> Result<()> {
+        if self.slot.is_some() && self.cursor.is_some() {
+            return Err(Error::InvalidQuery(
+                "`slot` and `cursor` are mutually exclusive".to_string(),
+            ));
         }
-        if let Some(builder_pubkey) = &self.builder_pubkey {
-            query.push_str(&format!("builder_pubkey={}&", builder_pubkey));
-        }
-        if let Some(order_by) = &self.order_by {
-            query.push_str(&format!("order_by={}&", order_by));
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for PayloadDeliveredQueryOptions {
+    /// Formats the options as a URL-encoded query string, without the leading `?`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&serde_urlencoded::to_string(self).map_err(|_| fmt::Error)?)
+    }
+}
+
+/// Sort order of the payloads delivered by a relay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderBy {
+    /// Lowest value first.
+    #[serde(rename = "value")]
+    Value,
+    /// Highest value first.
+    #[serde(rename = "-value")]
+    ValueDesc,
+}
+
+impl fmt::Display for OrderBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Value => f.write_str("value"),
+            Self::ValueDesc => f.write_str("-value"),
         }
+    }
+}
+
+impl FromStr for OrderBy {
+    type Err = Error;
 
-        query
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "value" => Ok(Self::Value),
+            "-value" => Ok(Self::ValueDesc),
+            _ => Err(Error::InvalidQuery(format!(
+                "`order_by` must be `value` or `-value`, got `{s}`"
+            ))),
+        }
     }
 }
 
-/// Entry for the validator payload delivered response.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+/// Bid trace of a block submitted by a builder to a relay.
+///
+/// [Visit the docs](https://flashbots.github.io/relay-specs/#/Builder/submitBlock) for more info.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
 #[allow(missing_docs)]
-pub struct PayloadBidtrace {
-    #[serde(deserialize_with = "deserialize_number_from_string")]
+pub struct BidTrace {
+    #[serde(with = "quoted_u64")]
     pub slot: u64,
-    pub parent_hash: String,
-    pub block_hash: String,
-    pub builder_pubkey: String,
-    pub proposer_pubkey: String,
-    pub proposer_fee_recipient: String,
-    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub parent_hash: Hash32,
+    pub block_hash: Hash32,
+    pub builder_pubkey: BlsPublicKey,
+    pub proposer_pubkey: BlsPublicKey,
+    pub proposer_fee_recipient: Address,
+    #[serde(with = "quoted_u64")]
     pub gas_limit: u64,
-    #[serde(deserialize_with = "deserialize_number_from_string")]
+    #[serde(with = "quoted_u64")]
     pub gas_used: u64,
-    pub value: String,
+    pub value: Wei,
+}
+
+/// Entry for the validator payload delivered response.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[allow(missing_docs)]
+pub struct PayloadBidtrace {
+    #[serde(flatten)]
+    pub bid_trace: BidTrace,
     #[serde(deserialize_with = "deserialize_number_from_string")]
     pub num_tx: u64,
     #[serde(deserialize_with = "deserialize_number_from_string")]
@@ -110,42 +158,50 @@ pub struct PayloadBidtrace {
 }
 
 /// Filter arguments for the get builder blocks bidtraces relay query
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Serialize)]
 pub struct BuilderBidsReceivedOptions {
     /// A specific slot number.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub slot: Option<u64>,
     /// A block hash.
-    pub block_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<Hash32>,
     /// A specific block number.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub block_number: Option<u64>,
     /// A specific builder public key.
-    pub builder_pubkey: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_pubkey: Option<BlsPublicKey>,
     /// The number of results.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<u64>,
 }
 
-impl ToString for BuilderBidsReceivedOptions {
-    fn to_string(&self) -> String {
-        let mut query = String::new();
-        query.push('?');
-
-        if let Some(slot) = self.slot {
-            query.push_str(&format!("slot={}&", slot));
-        }
-        if let Some(block_hash) = &self.block_hash {
-            query.push_str(&format!("block_hash={}&", block_hash));
-        }
-        if let Some(block_number) = self.block_number {
-            query.push_str(&format!("block_number={}&", block_number));
-        }
-        if let Some(builder_pubkey) = &self.builder_pubkey {
-            query.push_str(&format!("builder_pubkey={}&", builder_pubkey));
-        }
-        if let Some(limit) = self.limit {
-            query.push_str(&format!("limit={}&", limit));
+impl BuilderBidsReceivedOptions {
+    /// Check that the options form a valid query.
+    ///
+    /// Returns [`Error::InvalidQuery`] unless at least one of `slot`, `block_hash`,
+    /// `block_number` or `builder_pubkey` is set, as required by the relay specs.
+    pub fn validate(&self) -> Result<()> {
+        if self.slot.is_none()
+            && self.block_hash.is_none()
+            && self.block_number.is_none()
+            && self.builder_pubkey.is_none()
+        {
+            return Err(Error::InvalidQuery(
+                "one of `slot`, `block_hash`, `block_number` or `builder_pubkey` is required"
+                    .to_string(),
+            ));
         }
 
-        query
+        Ok(())
+    }
+}
+
+impl fmt::Display for BuilderBidsReceivedOptions {
+    /// Formats the options as a URL-encoded query string, without the leading `?`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&serde_urlencoded::to_string(self).map_err(|_| fmt::Error)?)
     }
 }
 
@@ -160,3 +216,207 @@ pub struct BuilderBlockBidtrace {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub optimistic_submission: Option<bool>,
 }
+
+impl BuilderBlockBidtrace {
+    /// Returns the number of milliseconds between the start of the slot of the bid and its
+    /// submission to the relay. Negative for bids submitted before the start of their slot.
+    pub fn ms_into_slot(&self, spec: &ChainSpec) -> i64 {
+        spec.slot_clock()
+            .ms_into_slot(self.payload.bid_trace.slot, self.timestamp_ms)
+    }
+}
+
+/// Block submitted by a builder to a relay, for the Capella fork.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct SubmitBlockRequestCapella {
+    pub message: BidTrace,
+    pub execution_payload: ExecutionPayloadCapella,
+    pub signature: BlsSignature,
+}
+
+/// Block submitted by a builder to a relay, for the Deneb fork.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct SubmitBlockRequestDeneb {
+    pub message: BidTrace,
+    pub execution_payload: ExecutionPayloadDeneb,
+    pub blobs_bundle: BlobsBundle,
+    pub signature: BlsSignature,
+}
+
+/// Block submitted by a builder to a relay, for the Electra fork.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct SubmitBlockRequestElectra {
+    pub message: BidTrace,
+    pub execution_payload: ExecutionPayloadDeneb,
+    pub blobs_bundle: BlobsBundle,
+    pub execution_requests: ExecutionRequests,
+    pub signature: BlsSignature,
+}
+
+/// Block submitted by a builder to a relay.
+///
+/// Serialized as the request alone; its fork is sent in the `Eth-Consensus-Version` header.
+///
+/// [Visit the docs](https://flashbots.github.io/relay-specs/#/Builder/submitBlock) for more info.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum SubmitBlockRequest {
+    /// A Capella block submission.
+    Capella(SubmitBlockRequestCapella),
+    /// A Deneb block submission.
+    Deneb(SubmitBlockRequestDeneb),
+    /// An Electra block submission.
+    Electra(SubmitBlockRequestElectra),
+}
+
+impl SubmitBlockRequest {
+    /// Returns the fork of the submitted block.
+    pub fn fork(&self) -> Fork {
+        match self {
+            Self::Capella(_) => Fork::Capella,
+            Self::Deneb(_) => Fork::Deneb,
+            Self::Electra(_) => Fork::Electra,
+        }
+    }
+
+    /// Returns the bid trace of the submitted block.
+    pub fn bid_trace(&self) -> &BidTrace {
+        match self {
+            Self::Capella(request) => &request.message,
+            Self::Deneb(request) => &request.message,
+            Self::Electra(request) => &request.message,
+        }
+    }
+
+    /// Returns the blobs of the submitted block, from the Deneb fork onwards.
+    pub fn blobs_bundle(&self) -> Option<&BlobsBundle> {
+        match self {
+            Self::Capella(_) => None,
+            Self::Deneb(request) => Some(&request.blobs_bundle),
+            Self::Electra(request) => Some(&request.blobs_bundle),
+        }
+    }
+}
+
+/// Encoding of the body of a block submission.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SubmissionEncoding {
+    /// JSON, as defined in the relay specs.
+    #[default]
+    Json,
+    /// SSZ, which is smaller and faster to decode for relays that support it.
+    Ssz,
+}
+
+/// Options for submitting a block to a relay.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SubmitBlockOptions {
+    /// Allow the submission to replace a more valuable bid previously submitted by the builder
+    /// for the same slot, which is otherwise rejected.
+    pub cancellations: bool,
+    /// Encoding of the request body.
+    pub encoding: SubmissionEncoding,
+    /// Compress the request body with gzip.
+    pub gzip: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BuilderBidsReceivedOptions, BuilderBlockBidtrace, OrderBy, PayloadBidtrace,
+        PayloadDeliveredQueryOptions,
+    };
+    use crate::{primitives::BlsPublicKey, Error, Network};
+
+    const PAYLOAD_BIDTRACE: &str = r#"{
+        "slot": "1",
+        "parent_hash": "0xcf8e0d4e9587369b2301d0790347320302cc0943d5a1884560367e8208d920f2",
+        "block_hash": "0xcf8e0d4e9587369b2301d0790347320302cc0943d5a1884560367e8208d920f2",
+        "builder_pubkey": "0x93247f2209abcacf57b75a51dafae777f9dd38bc7053d1af526f220a7489a6d3a2753e5f3e8b1cfe39b56f43611df74a",
+        "proposer_pubkey": "0x93247f2209abcacf57b75a51dafae777f9dd38bc7053d1af526f220a7489a6d3a2753e5f3e8b1cfe39b56f43611df74a",
+        "proposer_fee_recipient": "0xabcf8e0d4e9587369b2301d0790347320302cc09",
+        "gas_limit": "1",
+        "gas_used": "1",
+        "value": "15000000000000000000",
+        "num_tx": "1",
+        "block_number": "1"
+    }"#;
+
+    #[test]
+    fn test_payload_bidtrace_typed_fields() {
+        let bidtrace: PayloadBidtrace = serde_json::from_str(PAYLOAD_BIDTRACE).unwrap();
+        assert_eq!(bidtrace.bid_trace.value.format_eth(), "15");
+        assert_eq!(
+            bidtrace.bid_trace.builder_pubkey,
+            bidtrace.bid_trace.proposer_pubkey
+        );
+
+        // Hashes with the wrong length are rejected at parse time.
+        let invalid = PAYLOAD_BIDTRACE.replace("0xcf8e0d4e", "0xcf8e0d");
+        assert!(serde_json::from_str::<PayloadBidtrace>(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_builder_block_bidtrace_ms_into_slot() {
+        // Slot 1 of mainnet started at 1606824035.
+        let json = PAYLOAD_BIDTRACE.replacen(
+            "{",
+            r#"{ "timestamp_ms": "1606824034250", "optimistic_submission": false,"#,
+            1,
+        );
+        let bid: BuilderBlockBidtrace = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(bid.ms_into_slot(&Network::Mainnet.chain_spec()), -750);
+    }
+
+    #[test]
+    fn test_query_options_encoding() {
+        let opts = PayloadDeliveredQueryOptions {
+            cursor: Some(100),
+            limit: Some(10),
+            proposer_pubkey: Some(BlsPublicKey::repeat_byte(0xab)),
+            order_by: Some(OrderBy::ValueDesc),
+            ..Default::default()
+        };
+        assert_eq!(
+            opts.to_string(),
+            format!(
+                "cursor=100&limit=10&proposer_pubkey=0x{}&order_by=-value",
+                "ab".repeat(48)
+            )
+        );
+        assert_eq!(PayloadDeliveredQueryOptions::default().to_string(), "");
+
+        let opts = BuilderBidsReceivedOptions {
+            slot: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(opts.to_string(), "slot=1");
+    }
+
+    #[test]
+    fn test_query_options_validation() {
+        let opts = PayloadDeliveredQueryOptions {
+            slot: Some(1),
+            cursor: Some(2),
+            ..Default::default()
+        };
+        assert!(matches!(opts.validate(), Err(Error::InvalidQuery(_))));
+        assert!(PayloadDeliveredQueryOptions::default().validate().is_ok());
+
+        let opts = BuilderBidsReceivedOptions {
+            limit: Some(10),
+            ..Default::default()
+        };
+        assert!(matches!(opts.validate(), Err(Error::InvalidQuery(_))));
+
+        assert_eq!("-value".parse::<OrderBy>().unwrap(), OrderBy::ValueDesc);
+        assert!(matches!(
+            "value desc".parse::<OrderBy>(),
+            Err(Error::InvalidQuery(_))
+        ));
+    }
+}